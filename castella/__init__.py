@@ -1,5 +1,6 @@
 from .core import *
 
+from .async_image import AsyncImage
 from .async_net_image import AsyncNetImage
 from .box import Box
 from .button import Button, ButtonState
@@ -0,0 +1,59 @@
+from typing import Any, Callable, Optional, cast
+
+from castella.core import Painter, Point, SimpleValue, Size, SizePolicy, State, Widget
+
+# starts loading the image for a source, or returns it once it's ready. the
+# callback runs when the load finishes.
+ImageLoader = Callable[[Painter, str, Callable[[], None]], Optional[Any]]
+
+
+class AsyncImageBase(Widget):
+    def __init__(self, source: str | SimpleValue[str], loader: ImageLoader):
+        if isinstance(source, SimpleValue):
+            state = source
+        else:
+            state = State(source)
+        self._loader = loader
+
+        super().__init__(
+            state=state,
+            size=Size(0, 0),
+            pos=Point(0, 0),
+            pos_policy=None,
+            width_policy=SizePolicy.FIXED,
+            height_policy=SizePolicy.FIXED,
+        )
+
+    def redraw(self, p: Painter, _: bool) -> None:
+        state: SimpleValue[str] = cast(SimpleValue[str], self._state)
+        img = self._loader(p, state.value(), self.callback)
+        if img is None:
+            return
+        p.draw_image_object(img, 0, 0)
+
+    def callback(self) -> None:
+        if self.get_parent() is None:
+            self.update()
+        else:
+            self.ask_parent_to_render(True)
+
+    def width_policy(self, sp: SizePolicy):  # -> Self:
+        if sp is SizePolicy.CONTENT:
+            raise RuntimeError(
+                f"{type(self).__name__} doesn't accept SizePolicy.CONTENT"
+            )
+        return super().width_policy(sp)
+
+    def height_policy(self, sp: SizePolicy):  # -> Self:
+        if sp is SizePolicy.CONTENT:
+            raise RuntimeError(
+                f"{type(self).__name__} doesn't accept SizePolicy.CONTENT"
+            )
+        return super().height_policy(sp)
+
+
+class AsyncImage(AsyncImageBase):
+    def __init__(self, file_path: str | SimpleValue[str]):
+        super().__init__(
+            file_path, lambda p, path, callback: p.get_image_async(path, path, callback)
+        )
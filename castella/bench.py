@@ -0,0 +1,99 @@
+import time
+from dataclasses import dataclass
+from typing import Callable, Optional
+
+import skia
+
+from . import core
+from . import skia_painter
+
+DEFAULT_WIDTH = 800
+DEFAULT_HEIGHT = 600
+
+
+@dataclass(slots=True, frozen=True)
+class BenchResult:
+    name: str
+    count: int  # primitives drawn per frame
+    frames: int
+    total: float  # seconds
+
+    def per_frame(self) -> float:
+        return self.total / self.frames
+
+
+# just enough of core.Frame for the painter to draw offscreen
+class _OffscreenFrame:
+    def __init__(self, surface: skia.Surface) -> None:
+        self._surface = surface
+
+    def flush(self) -> None:
+        pass
+
+    def clear(self) -> None:
+        self._surface.getCanvas().clear(skia.ColorWHITE)
+
+
+def run(
+    name: str,
+    count: int,
+    draw: Callable[[core.Painter, int], None],
+    frames: int = 10,
+    surface: Optional[skia.Surface] = None,
+) -> BenchResult:
+    if surface is None:
+        surface = skia.Surface(DEFAULT_WIDTH, DEFAULT_HEIGHT)
+    p = skia_painter.Painter(_OffscreenFrame(surface), surface)
+    p.style(core.Style())
+
+    started = time.perf_counter()
+    for _ in range(frames):
+        p.clear_all()
+        for i in range(count):
+            draw(p, i)
+        p.flush()
+    return BenchResult(name, count, frames, time.perf_counter() - started)
+
+
+def bench_rects(
+    count: int = 1000, frames: int = 10, surface: Optional[skia.Surface] = None
+) -> BenchResult:
+    return run(
+        "rects",
+        count,
+        lambda p, i: p.fill_rect(core.Rect(_scatter(i), core.Size(20, 20))),
+        frames,
+        surface,
+    )
+
+
+def bench_text(
+    count: int = 1000, frames: int = 10, surface: Optional[skia.Surface] = None
+) -> BenchResult:
+    return run(
+        "text",
+        count,
+        lambda p, i: p.fill_text(f"label {i}", _scatter(i), None),
+        frames,
+        surface,
+    )
+
+
+def bench_images(
+    file_path: str,
+    count: int = 100,
+    frames: int = 10,
+    surface: Optional[skia.Surface] = None,
+) -> BenchResult:
+    return run(
+        "images",
+        count,
+        lambda p, i: p.draw_image(file_path, core.Rect(_scatter(i), core.Size(32, 32))),
+        frames,
+        surface,
+    )
+
+
+# spreads primitives deterministically so results are comparable between runs
+def _scatter(i: int) -> core.Point:
+    return core.Point(i * 37 % DEFAULT_WIDTH, i * 53 % DEFAULT_HEIGHT)
@@ -0,0 +1,68 @@
+import numpy as np
+
+_CHARACTERS = (
+    "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz#$%*+,-.:;=?@[]^_{|}~"
+)
+_INDEX = {c: i for i, c in enumerate(_CHARACTERS)}
+
+
+def decode(blurhash: str, width: int, height: int, punch: float = 1.0) -> np.ndarray:
+    if len(blurhash) < 6:
+        raise ValueError("BlurHash must be at least 6 characters long")
+
+    size_flag = _decode83(blurhash[0])
+    num_y = size_flag // 9 + 1
+    num_x = size_flag % 9 + 1
+    if len(blurhash) != 4 + 2 * num_x * num_y:
+        raise ValueError(
+            f"BlurHash length {len(blurhash)} doesn't match "
+            f"{num_x}x{num_y} components"
+        )
+
+    max_value = (_decode83(blurhash[1]) + 1) / 166 * punch
+    colors = np.empty((num_x * num_y, 3))
+    colors[0] = _decode_dc(_decode83(blurhash[2:6]))
+    for i in range(1, num_x * num_y):
+        colors[i] = _decode_ac(_decode83(blurhash[4 + i * 2 : 6 + i * 2]), max_value)
+    colors = colors.reshape(num_y, num_x, 3)
+
+    cos_x = np.cos(np.pi * np.outer(np.arange(num_x), np.arange(width)) / width)
+    cos_y = np.cos(np.pi * np.outer(np.arange(num_y), np.arange(height)) / height)
+    linear = np.einsum("jh,iw,jic->hwc", cos_y, cos_x, colors)
+
+    pixels = np.full((height, width, 4), 255, dtype=np.uint8)
+    pixels[:, :, :3] = _linear_to_srgb(linear)
+    return pixels
+
+
+def _decode83(value: str) -> int:
+    result = 0
+    for c in value:
+        if c not in _INDEX:
+            raise ValueError(f"Invalid BlurHash character: {c!r}")
+        result = result * 83 + _INDEX[c]
+    return result
+
+
+def _decode_dc(value: int) -> np.ndarray:
+    rgb = np.array([value >> 16, (value >> 8) & 255, value & 255], dtype=np.float64)
+    return _srgb_to_linear(rgb)
+
+
+def _decode_ac(value: int, max_value: float) -> np.ndarray:
+    quant = np.array(
+        [value // (19 * 19), (value // 19) % 19, value % 19], dtype=np.float64
+    )
+    normalized = (quant - 9) / 9
+    return np.sign(normalized) * normalized**2 * max_value
+
+
+def _srgb_to_linear(value: np.ndarray) -> np.ndarray:
+    v = value / 255
+    return np.where(v <= 0.04045, v / 12.92, ((v + 0.055) / 1.055) ** 2.4)
+
+
+def _linear_to_srgb(value: np.ndarray) -> np.ndarray:
+    v = np.clip(value, 0, 1)
+    srgb = np.where(v <= 0.0031308, v * 12.92, 1.055 * v ** (1 / 2.4) - 0.055)
+    return (srgb * 255 + 0.5).astype(np.uint8)
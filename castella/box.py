@@ -296,24 +296,14 @@ class Box(Layout):
         return p + Point(self._scroll_x, self._scroll_y)
 
     def contain_in_content_area(self, p: Point) -> bool:
-        return (
-            self._pos.x
-            < p.x
-            < self._pos.x
-            + self._size.width
-            - (0 if self._scroll_box_y is None else SCROLL_BAR_SIZE)
-        ) and (
-            self._pos.y
-            < p.y
-            < self._pos.y
-            + self._size.height
-            - (0 if self._scroll_box_x is None else SCROLL_BAR_SIZE)
+        scroll_bars = Size(
+            0 if self._scroll_box_y is None else SCROLL_BAR_SIZE,
+            0 if self._scroll_box_x is None else SCROLL_BAR_SIZE,
         )
+        return Rect(self._pos, self._size - scroll_bars).contain_strictly(p)
 
     def contain_in_my_area(self, p: Point) -> bool:
-        return (self._pos.x < p.x < self._pos.x + self._size.width) and (
-            self._pos.y < p.y < self._pos.y + self._size.height
-        )
+        return Rect(self._pos, self._size).contain_strictly(p)
 
     def _relocate_children(self, p: Painter) -> None:
         if len(self._children) == 0:
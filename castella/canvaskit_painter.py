@@ -1,21 +1,17 @@
-from typing import Optional, cast
+from collections.abc import Sequence
+from typing import BinaryIO, Optional, cast
 
 from js import Image, ImageData, window, document
 import numpy as np
-from pyodide import create_proxy
+from pyodide import create_proxy, to_js
 
 from . import core
+from .color import parse as parse_color
 
 
-def code2rgb(color_code):  # TODO alpha
-    r = int(color_code[1:3], 16)
-    g = int(color_code[3:5], 16)
-    b = int(color_code[5:7], 16)
-    return (r, g, b, 1.0)
-
-
-def to_ck_color(color: str) -> int:
-    return window.CK.Color(*code2rgb(color))
+def to_ck_color(color: str | core.Color) -> int:
+    r, g, b, a = parse_color(color)
+    return window.CK.Color(r, g, b, a / 255)
 
 
 def to_ck_rect(rect: core.Rect):
@@ -24,7 +20,165 @@ def to_ck_rect(rect: core.Rect):
     )
 
 
-class Painter:
+def to_ck_tile_mode(tile_mode: core.TileMode):
+    if tile_mode is core.TileMode.CLAMP:
+        return window.CK.TileMode.Clamp
+    elif tile_mode is core.TileMode.REPEAT:
+        return window.CK.TileMode.Repeat
+    elif tile_mode is core.TileMode.MIRROR:
+        return window.CK.TileMode.Mirror
+    else:
+        return window.CK.TileMode.Decal
+
+
+def to_ck_shader(gradient: core.Gradient):
+    colors = to_js([to_ck_color(stop.color) for stop in gradient.stops])
+    positions = to_js([stop.offset for stop in gradient.stops])
+    mode = to_ck_tile_mode(gradient.tile_mode)
+    if isinstance(gradient, core.LinearGradient):
+        return window.CK.Shader.MakeLinearGradient(
+            to_js([gradient.start.x, gradient.start.y]),
+            to_js([gradient.end.x, gradient.end.y]),
+            colors,
+            positions,
+            mode,
+        )
+    elif isinstance(gradient, core.RadialGradient):
+        return window.CK.Shader.MakeRadialGradient(
+            to_js([gradient.center.x, gradient.center.y]),
+            gradient.radius,
+            colors,
+            positions,
+            mode,
+        )
+    else:
+        return window.CK.Shader.MakeSweepGradient(
+            gradient.center.x,
+            gradient.center.y,
+            colors,
+            positions,
+            mode,
+            None,  # localMatrix
+            0,  # flags
+            gradient.start_angle,
+            gradient.end_angle,
+        )
+
+
+def to_ck_image_filter(f: Optional[core.ImageFilter]):
+    CK = window.CK
+    if f is None:
+        return None
+    elif isinstance(f, core.BlurFilter):
+        return CK.ImageFilter.MakeBlur(
+            f.sigma_x, f.sigma_y, CK.TileMode.Decal, to_ck_image_filter(f.input)
+        )
+    elif isinstance(f, core.DropShadowFilter):
+        return CK.ImageFilter.MakeDropShadow(
+            f.dx,
+            f.dy,
+            f.sigma,
+            f.sigma,
+            to_ck_color(f.color),
+            to_ck_image_filter(f.input),
+        )
+    elif isinstance(f, core.ColorMatrixFilter):
+        return CK.ImageFilter.MakeColorFilter(
+            CK.ColorFilter.MakeMatrix(to_js(list(f.matrix))),
+            to_ck_image_filter(f.input),
+        )
+    elif isinstance(f, core.DisplacementFilter):
+        return CK.ImageFilter.MakeDisplacementMap(
+            getattr(CK.ColorChannel, _CK_COLOR_CHANNELS[f.x_channel]),
+            getattr(CK.ColorChannel, _CK_COLOR_CHANNELS[f.y_channel]),
+            f.scale,
+            to_ck_image_filter(f.displacement),
+            to_ck_image_filter(f.input),
+        )
+    else:
+        return CK.ImageFilter.MakeBlend(
+            getattr(CK.BlendMode, _CK_BLEND_MODES[f.mode]),
+            to_ck_image_filter(f.background),
+            to_ck_image_filter(f.foreground),
+        )
+
+
+# CanvasKit's enums only exist once it has loaded, so they're looked up by name
+_CK_BLEND_MODES = {
+    core.BlendMode.SRC_OVER: "SrcOver",
+    core.BlendMode.MULTIPLY: "Multiply",
+    core.BlendMode.SCREEN: "Screen",
+    core.BlendMode.OVERLAY: "Overlay",
+    core.BlendMode.DARKEN: "Darken",
+    core.BlendMode.LIGHTEN: "Lighten",
+}
+
+_CK_COLOR_CHANNELS = {
+    core.ColorChannel.R: "Red",
+    core.ColorChannel.G: "Green",
+    core.ColorChannel.B: "Blue",
+    core.ColorChannel.A: "Alpha",
+}
+
+
+def to_ck_poly(points: Sequence[core.Point], close: bool):
+    path = window.CK.Path.new()
+    path.addPoly(to_js([c for p in points for c in (p.x, p.y)]), close)
+    return path
+
+
+def to_ck_path(path: core.Path):
+    ck_path = window.CK.Path.new()
+    for segment in path.segments:
+        if isinstance(segment, core.MoveTo):
+            ck_path.moveTo(segment.to.x, segment.to.y)
+        elif isinstance(segment, core.LineTo):
+            ck_path.lineTo(segment.to.x, segment.to.y)
+        elif isinstance(segment, core.QuadTo):
+            c = segment.control
+            ck_path.quadTo(c.x, c.y, segment.to.x, segment.to.y)
+        elif isinstance(segment, core.CubicTo):
+            c1, c2 = segment.control1, segment.control2
+            ck_path.cubicTo(c1.x, c1.y, c2.x, c2.y, segment.to.x, segment.to.y)
+        elif isinstance(segment, core.ArcTo):
+            c = segment.corner
+            ck_path.arcToTangent(c.x, c.y, segment.to.x, segment.to.y, segment.radius)
+        else:
+            ck_path.close()
+    return ck_path
+
+
+def make_stroke_paint(style: core.Style):
+    paint = window.CK.Paint.new()
+    paint.setColor(to_ck_color(style.stroke.color))
+    paint.setStyle(window.CK.PaintStyle.Stroke)
+    paint.setStrokeWidth(style.line.width)
+    paint.setStrokeCap(to_ck_stroke_cap(style.line.cap))
+    paint.setAntiAlias(True)
+    return paint
+
+
+def to_ck_stroke_cap(cap: core.LineCap):
+    if cap is core.LineCap.BUTT:
+        return window.CK.StrokeCap.Butt
+    elif cap is core.LineCap.ROUND:
+        return window.CK.StrokeCap.Round
+    else:
+        return window.CK.StrokeCap.Square
+
+
+def make_fill_paint(fill: core.FillStyle):
+    paint = window.CK.Paint.new()
+    paint.setStyle(window.CK.PaintStyle.Fill)
+    if fill.gradient is not None:
+        paint.setShader(to_ck_shader(fill.gradient))
+        paint.setDither(fill.dither)
+    else:
+        paint.setColor(to_ck_color(fill.color))
+    return paint
+
+
+class Painter(core.PainterHelpers):
     def __init__(self, frame: core.Frame, surface):
         self._frame = frame
         self._surface = surface
@@ -38,9 +192,7 @@ class Painter:
 
     def fill_rect(self, rect: core.Rect) -> None:
         style = cast(core.Style, self._style)
-        paint = window.CK.Paint.new()
-        paint.setColor(to_ck_color(style.fill.color))
-        paint.setStyle(window.CK.PaintStyle.Fill)
+        paint = make_fill_paint(style.fill)
         sr = to_ck_rect(rect)
         self._canvas.drawRect(sr, paint)
 
@@ -52,11 +204,29 @@ class Painter:
         sr = to_ck_rect(rect)
         self._canvas.drawRect(sr, paint)
 
+    def fill_inset_rect(self, rect: core.Rect, insets: core.EdgeInsets) -> None:
+        self.fill_rect(rect.inset(insets))
+
+    def stroke_inset_rect(self, rect: core.Rect, insets: core.EdgeInsets) -> None:
+        self.stroke_rect(rect.inset(insets))
+
+    def stroke_outline(self, rect: core.Rect) -> None:
+        outline = cast(core.Style, self._style).outline
+        if outline is None:
+            return
+        paint = window.CK.Paint.new()
+        paint.setColor(to_ck_color(outline.color))
+        paint.setStyle(window.CK.PaintStyle.Stroke)
+        paint.setStrokeWidth(outline.width)
+        paint.setAntiAlias(True)
+        # a stroke is centered on its path
+        sr = to_ck_rect(rect.inflate(outline.offset + outline.width / 2))
+        radius = outline.radius + outline.offset + outline.width / 2
+        self._canvas.drawRRect(window.CK.RRectXY(sr, radius, radius), paint)
+
     def fill_circle(self, circle: core.Circle) -> None:
         style = cast(core.Style, self._style)
-        paint = window.CK.Paint.new()
-        paint.setColor(to_ck_color(style.fill.color))
-        paint.setStyle(window.CK.PaintStyle.Fill)
+        paint = make_fill_paint(style.fill)
         paint.setAntiAlias(True)
         c = circle.center
         self._canvas.drawCircle(c.x, c.y, circle.radius, paint)
@@ -70,6 +240,60 @@ class Painter:
         c = circle.center
         self._canvas.drawCircle(c.x, c.y, circle.radius, paint)
 
+    def draw_line(self, start: core.Point, end: core.Point) -> None:
+        paint = make_stroke_paint(cast(core.Style, self._style))
+        self._canvas.drawLine(start.x, start.y, end.x, end.y, paint)
+
+    def stroke_polyline(self, points: Sequence[core.Point]) -> None:
+        paint = make_stroke_paint(cast(core.Style, self._style))
+        self._canvas.drawPath(to_ck_poly(points, False), paint)
+
+    def fill_polygon(self, points: Sequence[core.Point]) -> None:
+        style = cast(core.Style, self._style)
+        paint = make_fill_paint(style.fill)
+        paint.setAntiAlias(True)
+        self._canvas.drawPath(to_ck_poly(points, True), paint)
+
+    def stroke_polygon(self, points: Sequence[core.Point], close: bool = True) -> None:
+        paint = make_stroke_paint(cast(core.Style, self._style))
+        self._canvas.drawPath(to_ck_poly(points, close), paint)
+
+    def draw_arc(
+        self,
+        circle: core.Circle,
+        start_angle: float,
+        sweep_angle: float,
+        mode: core.ArcMode = core.ArcMode.OPEN,
+    ) -> None:
+        style = cast(core.Style, self._style)
+        c = circle.center
+        r = circle.radius
+        oval = window.CK.LTRBRect(c.x - r, c.y - r, c.x + r, c.y + r)
+        if mode is core.ArcMode.OPEN:
+            paint = make_stroke_paint(style)
+            self._canvas.drawArc(oval, start_angle, sweep_angle, False, paint)
+            return
+
+        paint = make_fill_paint(style.fill)
+        paint.setAntiAlias(True)
+        if mode is core.ArcMode.PIE:
+            self._canvas.drawArc(oval, start_angle, sweep_angle, True, paint)
+        else:
+            chord = window.CK.Path.new()
+            chord.addArc(oval, start_angle, sweep_angle)
+            chord.close()
+            self._canvas.drawPath(chord, paint)
+
+    def fill_path(self, path: core.Path) -> None:
+        style = cast(core.Style, self._style)
+        paint = make_fill_paint(style.fill)
+        paint.setAntiAlias(True)
+        self._canvas.drawPath(to_ck_path(path), paint)
+
+    def stroke_path(self, path: core.Path) -> None:
+        paint = make_stroke_paint(cast(core.Style, self._style))
+        self._canvas.drawPath(to_ck_path(path), paint)
+
     def measure_text(self, text: str) -> float:
         style = cast(core.Style, self._style)
         font = window.CK.Font.new(window.typeface, style.font.size)
@@ -82,6 +306,9 @@ class Painter:
     def translate(self, pos: core.Point) -> None:
         self._canvas.translate(pos.x, pos.y)
 
+    def transform(self, transform: core.Transform) -> None:
+        self._canvas.concat(to_js(list(transform.matrix)))
+
     def clip(self, rect: core.Rect) -> None:
         self._canvas.clipRect(
             to_ck_rect(
@@ -100,18 +327,17 @@ class Painter:
 
         style = cast(core.Style, self._style)
         if style is None or style.fill is None:
-            color = 0
+            paint = window.CK.Paint.new()
+            paint.setColor(0)
+            paint.setStyle(window.CK.PaintStyle.Fill)
         else:
-            color = to_ck_color(style.fill.color)
+            paint = make_fill_paint(style.fill)
 
         if style is None or style.font is None:
             font = window.CK.Font.new(window.typeface, 0)
         else:
             font = window.CK.Font.new(window.typeface, style.font.size)
         blob = window.CK.TextBlob.MakeFromText(text, font)
-        paint = window.CK.Paint.new()
-        paint.setColor(color)
-        paint.setStyle(window.CK.PaintStyle.Fill)
         paint.setAntiAlias(True)
         self._canvas.drawTextBlob(
             blob,
@@ -126,13 +352,38 @@ class Painter:
         ...
 
     def draw_image(
-        self, file_path: str, rect: core.Rect, use_cache: bool = True
+        self, file_path: core.FilePath, rect: core.Rect, use_cache: bool = True
+    ) -> None:
+        raise NotImplementedError()
+
+    def measure_image(
+        self, file_path: core.FilePath, use_cache: bool = True
+    ) -> core.Size:
+        raise NotImplementedError()
+
+    def draw_image_tiled(
+        self,
+        file_path: core.FilePath,
+        rect: core.Rect,
+        tile_mode: core.TileMode = core.TileMode.REPEAT,
+        use_cache: bool = True,
     ) -> None:
         raise NotImplementedError()
 
-    def measure_image(self, file_path: str, use_cache: bool = True) -> core.Size:
+    def load_svg(self, source: core.FilePath | bytes) -> core.SvgDocument:
+        raise NotImplementedError()
+
+    def draw_svg(
+        self,
+        svg: core.FilePath | core.SvgDocument,
+        rect: core.Rect,
+        use_cache: bool = True,
+    ) -> None:
         raise NotImplementedError()
 
+    def get_image_async(self, name, file_path, callback):
+        return self.get_net_image_async(name, file_path, callback)
+
     def get_net_image_async(self, name, url, callback):
         if name in self._images:
             return self._images[name]
@@ -168,6 +419,20 @@ class Painter:
     ) -> None:
         raise NotImplementedError()
 
+    def read_pixels_into(self, buffer) -> None:
+        raise NotImplementedError()
+
+    def to_numpy(self) -> np.ndarray:
+        raise NotImplementedError()
+
+    def save_image(
+        self,
+        file: core.FilePath | BinaryIO,
+        format: core.ImageFormat = core.ImageFormat.PNG,
+        quality: int = 90,
+    ) -> None:
+        raise NotImplementedError()
+
     def get_numpy_image_async(self, array, callback):
         arr_id = id(array)
         if arr_id in self._images:
@@ -196,6 +461,20 @@ class Painter:
         self._canvas.restore()
         self._style = self._style_stack.pop()
 
+    def save_layer(
+        self,
+        bounds: Optional[core.Rect] = None,
+        filter: Optional[core.ImageFilter] = None,
+    ) -> None:
+        paint = window.CK.Paint.new()
+        paint.setImageFilter(to_ck_image_filter(filter))
+        ck_rect = None if bounds is None else to_ck_rect(bounds)
+        self._canvas.saveLayer(paint, ck_rect)
+        self._style_stack.append(self._style)
+
+    def save_count(self) -> int:
+        return len(self._style_stack)
+
     def style(self, style: core.Style) -> None:
         self._style = style
 
@@ -1,3 +1,6 @@
+from dataclasses import dataclass, replace
+from functools import lru_cache
+
 LIGHT_MODE = 0
 DARK_MODE = 1
 
@@ -367,3 +370,299 @@ COLOR_SCHEMA = (
         "border-warning": palette["yellow"]["700"],
     },
 )
+
+
+# https://www.w3.org/TR/css-color-4/#named-colors
+NAMED_COLORS = {
+    "aliceblue": "#f0f8ff",
+    "antiquewhite": "#faebd7",
+    "aqua": "#00ffff",
+    "aquamarine": "#7fffd4",
+    "azure": "#f0ffff",
+    "beige": "#f5f5dc",
+    "bisque": "#ffe4c4",
+    "black": "#000000",
+    "blanchedalmond": "#ffebcd",
+    "blue": "#0000ff",
+    "blueviolet": "#8a2be2",
+    "brown": "#a52a2a",
+    "burlywood": "#deb887",
+    "cadetblue": "#5f9ea0",
+    "chartreuse": "#7fff00",
+    "chocolate": "#d2691e",
+    "coral": "#ff7f50",
+    "cornflowerblue": "#6495ed",
+    "cornsilk": "#fff8dc",
+    "crimson": "#dc143c",
+    "cyan": "#00ffff",
+    "darkblue": "#00008b",
+    "darkcyan": "#008b8b",
+    "darkgoldenrod": "#b8860b",
+    "darkgray": "#a9a9a9",
+    "darkgreen": "#006400",
+    "darkgrey": "#a9a9a9",
+    "darkkhaki": "#bdb76b",
+    "darkmagenta": "#8b008b",
+    "darkolivegreen": "#556b2f",
+    "darkorange": "#ff8c00",
+    "darkorchid": "#9932cc",
+    "darkred": "#8b0000",
+    "darksalmon": "#e9967a",
+    "darkseagreen": "#8fbc8f",
+    "darkslateblue": "#483d8b",
+    "darkslategray": "#2f4f4f",
+    "darkslategrey": "#2f4f4f",
+    "darkturquoise": "#00ced1",
+    "darkviolet": "#9400d3",
+    "deeppink": "#ff1493",
+    "deepskyblue": "#00bfff",
+    "dimgray": "#696969",
+    "dimgrey": "#696969",
+    "dodgerblue": "#1e90ff",
+    "firebrick": "#b22222",
+    "floralwhite": "#fffaf0",
+    "forestgreen": "#228b22",
+    "fuchsia": "#ff00ff",
+    "gainsboro": "#dcdcdc",
+    "ghostwhite": "#f8f8ff",
+    "gold": "#ffd700",
+    "goldenrod": "#daa520",
+    "gray": "#808080",
+    "green": "#008000",
+    "greenyellow": "#adff2f",
+    "grey": "#808080",
+    "honeydew": "#f0fff0",
+    "hotpink": "#ff69b4",
+    "indianred": "#cd5c5c",
+    "indigo": "#4b0082",
+    "ivory": "#fffff0",
+    "khaki": "#f0e68c",
+    "lavender": "#e6e6fa",
+    "lavenderblush": "#fff0f5",
+    "lawngreen": "#7cfc00",
+    "lemonchiffon": "#fffacd",
+    "lightblue": "#add8e6",
+    "lightcoral": "#f08080",
+    "lightcyan": "#e0ffff",
+    "lightgoldenrodyellow": "#fafad2",
+    "lightgray": "#d3d3d3",
+    "lightgreen": "#90ee90",
+    "lightgrey": "#d3d3d3",
+    "lightpink": "#ffb6c1",
+    "lightsalmon": "#ffa07a",
+    "lightseagreen": "#20b2aa",
+    "lightskyblue": "#87cefa",
+    "lightslategray": "#778899",
+    "lightslategrey": "#778899",
+    "lightsteelblue": "#b0c4de",
+    "lightyellow": "#ffffe0",
+    "lime": "#00ff00",
+    "limegreen": "#32cd32",
+    "linen": "#faf0e6",
+    "magenta": "#ff00ff",
+    "maroon": "#800000",
+    "mediumaquamarine": "#66cdaa",
+    "mediumblue": "#0000cd",
+    "mediumorchid": "#ba55d3",
+    "mediumpurple": "#9370db",
+    "mediumseagreen": "#3cb371",
+    "mediumslateblue": "#7b68ee",
+    "mediumspringgreen": "#00fa9a",
+    "mediumturquoise": "#48d1cc",
+    "mediumvioletred": "#c71585",
+    "midnightblue": "#191970",
+    "mintcream": "#f5fffa",
+    "mistyrose": "#ffe4e1",
+    "moccasin": "#ffe4b5",
+    "navajowhite": "#ffdead",
+    "navy": "#000080",
+    "oldlace": "#fdf5e6",
+    "olive": "#808000",
+    "olivedrab": "#6b8e23",
+    "orange": "#ffa500",
+    "orangered": "#ff4500",
+    "orchid": "#da70d6",
+    "palegoldenrod": "#eee8aa",
+    "palegreen": "#98fb98",
+    "paleturquoise": "#afeeee",
+    "palevioletred": "#db7093",
+    "papayawhip": "#ffefd5",
+    "peachpuff": "#ffdab9",
+    "peru": "#cd853f",
+    "pink": "#ffc0cb",
+    "plum": "#dda0dd",
+    "powderblue": "#b0e0e6",
+    "purple": "#800080",
+    "rebeccapurple": "#663399",
+    "red": "#ff0000",
+    "rosybrown": "#bc8f8f",
+    "royalblue": "#4169e1",
+    "saddlebrown": "#8b4513",
+    "salmon": "#fa8072",
+    "sandybrown": "#f4a460",
+    "seagreen": "#2e8b57",
+    "seashell": "#fff5ee",
+    "sienna": "#a0522d",
+    "silver": "#c0c0c0",
+    "skyblue": "#87ceeb",
+    "slateblue": "#6a5acd",
+    "slategray": "#708090",
+    "slategrey": "#708090",
+    "snow": "#fffafa",
+    "springgreen": "#00ff7f",
+    "steelblue": "#4682b4",
+    "tan": "#d2b48c",
+    "teal": "#008080",
+    "thistle": "#d8bfd8",
+    "tomato": "#ff6347",
+    "turquoise": "#40e0d0",
+    "violet": "#ee82ee",
+    "wheat": "#f5deb3",
+    "white": "#ffffff",
+    "whitesmoke": "#f5f5f5",
+    "yellow": "#ffff00",
+    "yellowgreen": "#9acd32",
+    "transparent": "#00000000",
+}
+
+
+@dataclass(slots=True, frozen=True)
+class Color:
+    r: int
+    g: int
+    b: int
+    a: int = 255
+
+    @staticmethod
+    def parse(color: str) -> "Color":
+        return Color(*parse(color))
+
+    def to_hex(self) -> str:
+        if self.a == 255:
+            return f"#{self.r:02x}{self.g:02x}{self.b:02x}"
+        return f"#{self.r:02x}{self.g:02x}{self.b:02x}{self.a:02x}"
+
+    def with_alpha(self, alpha: float) -> "Color":
+        return replace(self, a=_clamp_byte(alpha * 255))
+
+    def mix(self, other: "Color", t: float) -> "Color":
+        return Color(
+            _clamp_byte(self.r + (other.r - self.r) * t),
+            _clamp_byte(self.g + (other.g - self.g) * t),
+            _clamp_byte(self.b + (other.b - self.b) * t),
+            _clamp_byte(self.a + (other.a - self.a) * t),
+        )
+
+    def lighten(self, amount: float) -> "Color":
+        h, s, l = _rgb_to_hsl(self.r, self.g, self.b)
+        r, g, b = _hsl_to_rgb(h, s, max(0.0, min(1.0, l + amount)))
+        return Color(r, g, b, self.a)
+
+    def darken(self, amount: float) -> "Color":
+        return self.lighten(-amount)
+
+
+# styles hold their colors as strings, so the same few are parsed on every draw
+@lru_cache(maxsize=1024)
+def parse(color: "str | Color") -> tuple[int, int, int, int]:
+    if isinstance(color, Color):
+        return color.r, color.g, color.b, color.a
+
+    c = color.strip().lower()
+    if c in NAMED_COLORS:
+        c = NAMED_COLORS[c]
+
+    if c.startswith("#"):
+        return _parse_hex(color, c[1:])
+
+    if c.endswith(")") and "(" in c:
+        name, args = c[:-1].split("(", 1)
+        name = name.strip()
+        # both the legacy "rgb(1, 2, 3, 0.5)" and modern "rgb(1 2 3 / 50%)" forms
+        values = args.replace(",", " ").replace("/", " ").split()
+        if name in ("rgb", "rgba") and len(values) in (3, 4):
+            r, g, b = (_parse_channel(color, v) for v in values[:3])
+            return r, g, b, _parse_alpha(color, values[3] if len(values) == 4 else "1")
+        if name in ("hsl", "hsla") and len(values) in (3, 4):
+            r, g, b = _hsl_to_rgb(
+                _parse_hue(color, values[0]),
+                _parse_percentage(color, values[1]),
+                _parse_percentage(color, values[2]),
+            )
+            return r, g, b, _parse_alpha(color, values[3] if len(values) == 4 else "1")
+
+    raise ValueError(f"Invalid color: {color!r}")
+
+
+def _parse_hex(color: str, digits: str) -> tuple[int, int, int, int]:
+    if len(digits) in (3, 4):
+        digits = "".join(d * 2 for d in digits)
+    if len(digits) == 6:
+        digits += "ff"
+    if len(digits) != 8 or any(d not in "0123456789abcdef" for d in digits):
+        raise ValueError(f"Invalid color: {color!r}")
+    value = int(digits, 16)
+    return (value >> 24) & 0xFF, (value >> 16) & 0xFF, (value >> 8) & 0xFF, value & 0xFF
+
+
+def _to_float(color: str, value: str) -> float:
+    try:
+        return float(value)
+    except ValueError:
+        raise ValueError(f"Invalid color: {color!r}") from None
+
+
+def _clamp_byte(value: float) -> int:
+    return max(0, min(255, round(value)))
+
+
+def _parse_channel(color: str, value: str) -> int:
+    if value.endswith("%"):
+        return _clamp_byte(_to_float(color, value[:-1]) * 255 / 100)
+    return _clamp_byte(_to_float(color, value))
+
+
+def _parse_alpha(color: str, value: str) -> int:
+    if value.endswith("%"):
+        return _clamp_byte(_to_float(color, value[:-1]) * 255 / 100)
+    return _clamp_byte(_to_float(color, value) * 255)
+
+
+def _parse_hue(color: str, value: str) -> float:
+    if value.endswith("deg"):
+        value = value[:-3]
+    return _to_float(color, value) % 360
+
+
+def _parse_percentage(color: str, value: str) -> float:
+    if not value.endswith("%"):
+        raise ValueError(f"Invalid color: {color!r}")
+    return max(0.0, min(1.0, _to_float(color, value[:-1]) / 100))
+
+
+def _rgb_to_hsl(r: int, g: int, b: int) -> tuple[float, float, float]:
+    r_, g_, b_ = r / 255, g / 255, b / 255
+    high = max(r_, g_, b_)
+    low = min(r_, g_, b_)
+    l = (high + low) / 2
+    d = high - low
+    if d == 0:
+        return 0.0, 0.0, l
+
+    s = d / (1 - abs(2 * l - 1))
+    if high == r_:
+        h = 60 * (((g_ - b_) / d) % 6)
+    elif high == g_:
+        h = 60 * ((b_ - r_) / d + 2)
+    else:
+        h = 60 * ((r_ - g_) / d + 4)
+    return h, s, l
+
+
+def _hsl_to_rgb(h: float, s: float, l: float) -> tuple[int, int, int]:
+    def f(n: int) -> int:
+        k = (n + h / 30) % 12
+        a = s * min(l, 1 - l)
+        return _clamp_byte((l - a * max(-1, min(k - 3, 9 - k, 1))) * 255)
+
+    return f(0), f(8), f(4)
@@ -206,15 +206,13 @@ class Column(Layout):
 
     def contain_in_content_area(self, p: Point) -> bool:
         if self._scrollable and self.content_height() > self.get_height():
-            return (
-                self._pos.x < p.x < self._pos.x + self._size.width - SCROLL_BAR_SIZE
-            ) and (self._pos.y < p.y < self._pos.y + self._size.height)
+            return Rect(
+                self._pos, self._size - Size(SCROLL_BAR_SIZE, 0)
+            ).contain_strictly(p)
         return self.contain_in_my_area(p)
 
     def contain_in_my_area(self, p: Point) -> bool:
-        return (self._pos.x < p.x < self._pos.x + self._size.width) and (
-            self._pos.y < p.y < self._pos.y + self._size.height
-        )
+        return Rect(self._pos, self._size).contain_strictly(p)
 
     def _relocate_children(self, p: Painter) -> None:
         self._resize_children(p)
@@ -1,12 +1,22 @@
+import difflib
+import json
+import math
+import os
 import sys
+import types
+import weakref
 from abc import ABC, abstractmethod
-from collections.abc import Iterable
+from collections.abc import Iterable, Iterator, Sequence
+from contextlib import contextmanager
 from copy import deepcopy
-from dataclasses import dataclass, replace
-from enum import Enum, IntEnum, auto
+from dataclasses import dataclass, field, fields, is_dataclass, replace
+from enum import Enum, Flag, IntEnum, auto
 from typing import (
     Any,
+    BinaryIO,
     Callable,
+    ContextManager,
+    ForwardRef,
     Generator,
     Generic,
     List,
@@ -15,12 +25,15 @@ from typing import (
     TypeAlias,
     TypeVar,
     Union,
+    get_args,
+    get_origin,
     runtime_checkable,
 )
 
 import numpy as np
 
 from . import color
+from .color import Color
 
 
 @dataclass(slots=True)
@@ -34,6 +47,14 @@ class Point:
     def __sub__(self, other: "Point") -> "Point":
         return Point(self.x - other.x, self.y - other.y)
 
+    def __mul__(self, factor: float) -> "Point":
+        return Point(self.x * factor, self.y * factor)
+
+    __rmul__ = __mul__
+
+    def __neg__(self) -> "Point":
+        return Point(-self.x, -self.y)
+
 
 @dataclass(slots=True)
 class Size:
@@ -46,17 +67,107 @@ class Size:
     def __sub__(self, other):
         return Size(self.width - other.width, self.height - other.height)
 
+    def __mul__(self, factor: float) -> "Size":
+        return Size(self.width * factor, self.height * factor)
+
+    __rmul__ = __mul__
+
+    def __neg__(self) -> "Size":
+        return Size(-self.width, -self.height)
+
+
+@dataclass(slots=True, frozen=True)
+class EdgeInsets:
+    top: float = 0
+    right: float = 0
+    bottom: float = 0
+    left: float = 0
+
+    @staticmethod
+    def all(value: float) -> "EdgeInsets":
+        return EdgeInsets(value, value, value, value)
+
+    @staticmethod
+    def symmetric(vertical: float = 0, horizontal: float = 0) -> "EdgeInsets":
+        return EdgeInsets(vertical, horizontal, vertical, horizontal)
+
+    def horizontal(self) -> float:
+        return self.left + self.right
+
+    def vertical(self) -> float:
+        return self.top + self.bottom
+
 
 @dataclass(slots=True, frozen=True)
 class Rect:
     origin: Point
     size: Size
 
+    @staticmethod
+    def from_ltrb(left: float, top: float, right: float, bottom: float) -> "Rect":
+        return Rect(Point(left, top), Size(right - left, bottom - top))
+
+    def to_ltrb(self) -> tuple[float, float, float, float]:
+        return (
+            self.origin.x,
+            self.origin.y,
+            self.origin.x + self.size.width,
+            self.origin.y + self.size.height,
+        )
+
+    def offset(self, delta: Point) -> "Rect":
+        return Rect(self.origin + delta, self.size)
+
     def contain(self, p: Point) -> bool:
         return (self.origin.x <= p.x <= self.origin.x + self.size.width) and (
             self.origin.y <= p.y <= self.origin.y + self.size.height
         )
 
+    # excludes the boundary, as widget hit testing does
+    def contain_strictly(self, p: Point) -> bool:
+        return (self.origin.x < p.x < self.origin.x + self.size.width) and (
+            self.origin.y < p.y < self.origin.y + self.size.height
+        )
+
+    def center(self) -> Point:
+        return Point(
+            self.origin.x + self.size.width / 2, self.origin.y + self.size.height / 2
+        )
+
+    def intersects(self, other: "Rect") -> bool:
+        return self.intersection(other) is not None
+
+    def intersection(self, other: "Rect") -> Optional["Rect"]:
+        l1, t1, r1, b1 = self.to_ltrb()
+        l2, t2, r2, b2 = other.to_ltrb()
+        left, top, right, bottom = max(l1, l2), max(t1, t2), min(r1, r2), min(b1, b2)
+        if right <= left or bottom <= top:
+            return None
+        return Rect.from_ltrb(left, top, right, bottom)
+
+    def union(self, other: "Rect") -> "Rect":
+        l1, t1, r1, b1 = self.to_ltrb()
+        l2, t2, r2, b2 = other.to_ltrb()
+        return Rect.from_ltrb(min(l1, l2), min(t1, t2), max(r1, r2), max(b1, b2))
+
+    def inflate(self, dx: float, dy: Optional[float] = None) -> "Rect":
+        if dy is None:
+            dy = dx
+        left, top, right, bottom = self.to_ltrb()
+        return Rect.from_ltrb(left - dx, top - dy, right + dx, bottom + dy)
+
+    def deflate(self, dx: float, dy: Optional[float] = None) -> "Rect":
+        return self.inflate(-dx, -dx if dy is None else -dy)
+
+    def inset(self, insets: EdgeInsets) -> "Rect":
+        left, top, right, bottom = self.to_ltrb()
+        return Rect.from_ltrb(
+            left + insets.left,
+            top + insets.top,
+            right - insets.right,
+            bottom - insets.bottom,
+        )
+
 
 @dataclass(slots=True, frozen=True)
 class Circle:
@@ -68,6 +179,141 @@ class Circle:
         return ((p.x - c.x) ** 2 + (p.y - c.y) ** 2) < self.radius**2
 
 
+@dataclass(slots=True, frozen=True)
+class MoveTo:
+    to: Point
+
+
+@dataclass(slots=True, frozen=True)
+class LineTo:
+    to: Point
+
+
+@dataclass(slots=True, frozen=True)
+class QuadTo:
+    control: Point
+    to: Point
+
+
+@dataclass(slots=True, frozen=True)
+class CubicTo:
+    control1: Point
+    control2: Point
+    to: Point
+
+
+# a circular arc of radius tangent to the lines from the current point to
+# corner and from corner to to, like arcTo on an HTML canvas
+@dataclass(slots=True, frozen=True)
+class ArcTo:
+    corner: Point
+    to: Point
+    radius: float
+
+
+@dataclass(slots=True, frozen=True)
+class ClosePath:
+    pass
+
+
+PathSegment: TypeAlias = Union[MoveTo, LineTo, QuadTo, CubicTo, ArcTo, ClosePath]
+
+
+@dataclass(slots=True)
+class Path:
+    segments: list[PathSegment] = field(default_factory=list)
+
+    def move_to(self, to: Point):  # -> Self:
+        self.segments.append(MoveTo(to))
+        return self
+
+    def line_to(self, to: Point):  # -> Self:
+        self.segments.append(LineTo(to))
+        return self
+
+    def quad_to(self, control: Point, to: Point):  # -> Self:
+        self.segments.append(QuadTo(control, to))
+        return self
+
+    def cubic_to(self, control1: Point, control2: Point, to: Point):  # -> Self:
+        self.segments.append(CubicTo(control1, control2, to))
+        return self
+
+    def arc_to(self, corner: Point, to: Point, radius: float):  # -> Self:
+        self.segments.append(ArcTo(corner, to, radius))
+        return self
+
+    def close(self):  # -> Self:
+        self.segments.append(ClosePath())
+        return self
+
+
+@dataclass(slots=True, frozen=True)
+class Transform:
+    # row-major 3x3 matrix; points are mapped as column vectors (x, y, 1)
+    matrix: tuple[float, ...] = (1, 0, 0, 0, 1, 0, 0, 0, 1)
+
+    @staticmethod
+    def translate(dx: float, dy: float) -> "Transform":
+        return Transform((1, 0, dx, 0, 1, dy, 0, 0, 1))
+
+    @staticmethod
+    def scale(sx: float, sy: Optional[float] = None) -> "Transform":
+        if sy is None:
+            sy = sx
+        return Transform((sx, 0, 0, 0, sy, 0, 0, 0, 1))
+
+    @staticmethod
+    def rotate(degrees: float) -> "Transform":
+        rad = math.radians(degrees)
+        c = math.cos(rad)
+        s = math.sin(rad)
+        return Transform((c, -s, 0, s, c, 0, 0, 0, 1))
+
+    @staticmethod
+    def skew(kx: float, ky: float) -> "Transform":
+        return Transform((1, kx, 0, ky, 1, 0, 0, 0, 1))
+
+    def multiply(self, other: "Transform") -> "Transform":
+        # the result applies other first, then self
+        a = self.matrix
+        b = other.matrix
+        return Transform(
+            tuple(
+                sum(a[row * 3 + k] * b[k * 3 + col] for k in range(3))
+                for row in range(3)
+                for col in range(3)
+            )
+        )
+
+    def __matmul__(self, other: "Transform") -> "Transform":
+        return self.multiply(other)
+
+    def invert(self) -> "Transform":
+        a, b, c, d, e, f, g, h, i = self.matrix
+        det = a * (e * i - f * h) - b * (d * i - f * g) + c * (d * h - e * g)
+        if det == 0:
+            raise ValueError(f"{self} is not invertible")
+        return Transform(
+            (
+                (e * i - f * h) / det,
+                (c * h - b * i) / det,
+                (b * f - c * e) / det,
+                (f * g - d * i) / det,
+                (a * i - c * g) / det,
+                (c * d - a * f) / det,
+                (d * h - e * g) / det,
+                (b * g - a * h) / det,
+                (a * e - b * d) / det,
+            )
+        )
+
+    def map_point(self, p: Point) -> Point:
+        a, b, c, d, e, f, g, h, i = self.matrix
+        w = g * p.x + h * p.y + i
+        return Point((a * p.x + b * p.y + c) / w, (d * p.x + e * p.y + f) / w)
+
+
 class SizePolicy(Enum):
     FIXED = auto()
     EXPANDING = auto()
@@ -100,14 +346,157 @@ class FontSlant(Enum):
     ITALIC = auto()
 
 
+class TileMode(Enum):
+    CLAMP = auto()
+    REPEAT = auto()
+    MIRROR = auto()
+    DECAL = auto()
+
+
+class ImageFormat(Enum):
+    PNG = auto()
+    JPEG = auto()
+    WEBP = auto()
+
+
+@dataclass(slots=True, frozen=True)
+class GradientStop:
+    offset: float
+    color: str | Color
+
+
+@dataclass(slots=True, frozen=True)
+class LinearGradient:
+    start: Point
+    end: Point
+    stops: tuple[GradientStop, ...]
+    tile_mode: TileMode = TileMode.CLAMP
+
+
+@dataclass(slots=True, frozen=True)
+class RadialGradient:
+    center: Point
+    radius: float
+    stops: tuple[GradientStop, ...]
+    tile_mode: TileMode = TileMode.CLAMP
+
+
+@dataclass(slots=True, frozen=True)
+class SweepGradient:
+    center: Point
+    stops: tuple[GradientStop, ...]
+    start_angle: float = 0
+    end_angle: float = 360
+    tile_mode: TileMode = TileMode.CLAMP
+
+
+Gradient: TypeAlias = LinearGradient | RadialGradient | SweepGradient
+
+
+class BlendMode(Enum):
+    SRC_OVER = auto()
+    MULTIPLY = auto()
+    SCREEN = auto()
+    OVERLAY = auto()
+    DARKEN = auto()
+    LIGHTEN = auto()
+
+
+class ColorChannel(Enum):
+    R = auto()
+    G = auto()
+    B = auto()
+    A = auto()
+
+
+# image filters compose into a graph through their inputs; an input of None
+# stands for the content being filtered, e.g. what was drawn into a layer
+@dataclass(slots=True, frozen=True)
+class BlurFilter:
+    sigma_x: float
+    sigma_y: float
+    input: Optional["ImageFilter"] = None
+
+
+@dataclass(slots=True, frozen=True)
+class DropShadowFilter:
+    dx: float
+    dy: float
+    sigma: float
+    color: str | Color = "#00000080"
+    input: Optional["ImageFilter"] = None
+
+
+# a row-major 4x5 matrix mapping RGBA to RGBA, with offsets in 0..1
+@dataclass(slots=True, frozen=True)
+class ColorMatrixFilter:
+    matrix: tuple[float, ...]
+    input: Optional["ImageFilter"] = None
+
+    @staticmethod
+    def saturation(
+        amount: float, input: Optional["ImageFilter"] = None
+    ) -> "ColorMatrixFilter":
+        # 0 is grayscale, 1 leaves colors unchanged
+        r, g, b = (w * (1 - amount) for w in (0.2126, 0.7152, 0.0722))
+        return ColorMatrixFilter(
+            (
+                (r + amount, g, b, 0, 0)
+                + (r, g + amount, b, 0, 0)
+                + (r, g, b + amount, 0, 0)
+                + (0, 0, 0, 1, 0)
+            ),
+            input,
+        )
+
+    @staticmethod
+    def brightness(
+        factor: float, input: Optional["ImageFilter"] = None
+    ) -> "ColorMatrixFilter":
+        # scales RGB, so values below 1 darken
+        return ColorMatrixFilter(
+            (
+                (factor, 0, 0, 0, 0)
+                + (0, factor, 0, 0, 0)
+                + (0, 0, factor, 0, 0)
+                + (0, 0, 0, 1, 0)
+            ),
+            input,
+        )
+
+
+# offsets each pixel of input by the chosen channels of displacement
+@dataclass(slots=True, frozen=True)
+class DisplacementFilter:
+    displacement: "ImageFilter"
+    scale: float
+    x_channel: ColorChannel = ColorChannel.R
+    y_channel: ColorChannel = ColorChannel.G
+    input: Optional["ImageFilter"] = None
+
+
+@dataclass(slots=True, frozen=True)
+class BlendFilter:
+    mode: BlendMode
+    background: Optional["ImageFilter"] = None
+    foreground: Optional["ImageFilter"] = None
+
+
+ImageFilter: TypeAlias = Union[
+    BlurFilter, DropShadowFilter, ColorMatrixFilter, DisplacementFilter, BlendFilter
+]
+
+
 @dataclass(slots=True, frozen=True)
 class FillStyle:
-    color: str = "black"
+    color: str | Color = "black"
+    gradient: Optional[Gradient] = None  # takes precedence over color when set
+    dither: bool = False  # smooths banding in large, soft gradients
 
 
 @dataclass(slots=True, frozen=True)
 class StrokeStyle:
-    color: str = "black"
+    color: str | Color = "black"
 
 
 class LineCap(Enum):
@@ -116,6 +505,12 @@ class LineCap(Enum):
     SQUARE = auto()
 
 
+class ArcMode(Enum):
+    OPEN = auto()  # just the curve
+    PIE = auto()  # closed through the center
+    CHORD = auto()  # closed by a straight line between the ends
+
+
 @dataclass(slots=True, frozen=True)
 class LineStyle:
     width: float = 1.0
@@ -148,6 +543,15 @@ class FontMetrics:
     cap_height: float
 
 
+# drawn outside the bounds it is given, so it never affects layout
+@dataclass(slots=True, frozen=True)
+class OutlineStyle:
+    color: str | Color = "#1e88e5"
+    width: float = 2.0
+    offset: float = 2.0  # gap between the bounds and the inner edge of the outline
+    radius: float = 0.0
+
+
 @dataclass(slots=True, frozen=True)
 class Style:
     fill: FillStyle = FillStyle()
@@ -155,6 +559,146 @@ class Style:
     line: LineStyle = LineStyle()
     font: Font = Font()
     padding: int = 8  # currently this value has the meaning only for Text and Button
+    outline: Optional[OutlineStyle] = None
+
+    @staticmethod
+    def from_dict(data: dict) -> "Style":
+        return _from_jsonable(Style, data)
+
+    @staticmethod
+    def from_json(text: str) -> "Style":
+        return Style.from_dict(json.loads(text))
+
+    def to_dict(self) -> dict:
+        return _to_jsonable(self)
+
+    # overrides uses the to_dict layout; keys not present keep this style's values
+    def merged(self, overrides: dict) -> "Style":
+        return Style.from_dict(_merge_dicts(self.to_dict(), overrides))
+
+    def to_json(self) -> str:
+        return json.dumps(self.to_dict())
+
+
+def _to_jsonable(value: Any) -> Any:
+    if isinstance(value, IntEnum):
+        return int(value)
+    if isinstance(value, Enum):
+        return value.name
+    if isinstance(value, (tuple, list)):
+        return [_to_jsonable(v) for v in value]
+    if isinstance(value, (*_LIVE_COMMANDS, SvgDocument)):
+        raise ValueError(
+            f"{type(value).__name__} holds a live image and can't be serialized"
+        )
+    # a Color is written as its fields too, so it reads back as a Color while a
+    # CSS string stays a string
+    if is_dataclass(value):
+        data = {f.name: _to_jsonable(getattr(value, f.name)) for f in fields(value)}
+        if type(value) in _KINDS:
+            data["kind"] = _KINDS[type(value)]
+        return data
+    return value
+
+
+# where names the value being loaded in error messages, e.g. "Style.fill"
+def _from_jsonable(tp: Any, data: Any, where: str = "value") -> Any:
+    tp = _resolve_type(tp)
+    origin = get_origin(tp)
+    if origin in (Union, types.UnionType):
+        args = _union_args(tp)
+        if data is None and type(None) in args:
+            return None
+        classes = [arg for arg in args if is_dataclass(arg)]
+        if isinstance(data, dict) and classes:
+            if len(classes) == 1 and "kind" not in data:
+                return _from_jsonable(classes[0], data, where)
+            kinds = {_KINDS[cls]: cls for cls in classes if cls in _KINDS}
+            expected = ", ".join(kinds)
+            if "kind" not in data:
+                raise ValueError(
+                    f"Invalid {where}: missing kind, expected one of {expected}"
+                )
+            if data["kind"] not in kinds:
+                raise ValueError(
+                    f"Invalid {where}: unknown kind {data['kind']!r}, "
+                    f"expected one of {expected}"
+                )
+            return _from_jsonable(kinds[data["kind"]], data, where)
+        # a plain value, such as a color given as a CSS string
+        for arg in args:
+            if not is_dataclass(arg) and arg is not type(None):
+                try:
+                    return _from_jsonable(arg, data, where)
+                except ValueError:
+                    pass
+        raise ValueError(f"Invalid {where}: got {data!r}")
+    if origin in (tuple, list):
+        if not isinstance(data, list):
+            raise ValueError(f"Invalid {where}: expected a list, got {data!r}")
+        item_type = get_args(tp)[0]
+        items = [
+            _from_jsonable(item_type, d, f"{where}[{i}]") for i, d in enumerate(data)
+        ]
+        return tuple(items) if origin is tuple else items
+    if isinstance(tp, type) and issubclass(tp, Enum):
+        if not isinstance(data, str) or data not in tp.__members__:
+            names = ", ".join(tp.__members__)
+            raise ValueError(
+                f"Invalid {tp.__name__}: {data!r}, expected one of {names}"
+            )
+        return tp[data]
+    if is_dataclass(tp):
+        if not isinstance(data, dict):
+            raise ValueError(
+                f"Invalid {where}: expected a {tp.__name__} object, got {data!r}"
+            )
+        unknown = set(data) - {f.name for f in fields(tp)} - {"kind"}
+        if unknown:
+            names = ", ".join(sorted(unknown))
+            raise ValueError(f"Unknown {tp.__name__} field(s): {names}")
+        return tp(
+            **{
+                f.name: _from_jsonable(f.type, data[f.name], f"{tp.__name__}.{f.name}")
+                for f in fields(tp)
+                if f.name in data
+            }
+        )
+    if tp in _SCALAR_TYPES and not _is_scalar(tp, data):
+        raise ValueError(
+            f"Invalid {where}: expected {_SCALAR_TYPES[tp]}, got {data!r}"
+        )
+    return data
+
+
+# a forward reference, as used by the recursive ImageFilter types
+def _resolve_type(tp: Any) -> Any:
+    if isinstance(tp, ForwardRef):
+        tp = tp.__forward_arg__
+    if isinstance(tp, str):
+        tp = globals()[tp]
+    return tp
+
+
+# flattened, since a Union can't flatten a forward reference to another one
+def _union_args(tp: Any) -> list[Any]:
+    args = []
+    for arg in map(_resolve_type, get_args(tp)):
+        if get_origin(arg) in (Union, types.UnionType):
+            args.extend(_union_args(arg))
+        else:
+            args.append(arg)
+    return args
+
+
+_SCALAR_TYPES = {bool: "a boolean", int: "a number", float: "a number", str: "a string"}
+
+
+def _is_scalar(tp: type, data: Any) -> bool:
+    if tp in (int, float):
+        # JSON has one number type, and font sizes may be fractional
+        return isinstance(data, (int, float)) and not isinstance(data, bool)
+    return isinstance(data, tp)
 
 
 @dataclass(slots=True, frozen=True)
@@ -170,6 +714,24 @@ class TextAlign(Enum):
     RIGHT = auto()
 
 
+FilePath: TypeAlias = str | os.PathLike[str]
+
+
+def as_path_str(path: FilePath) -> str:
+    p = os.fspath(path)
+    if not isinstance(p, str):
+        raise TypeError(f"Expected a str or os.PathLike[str] path, got {p!r}")
+    return p
+
+
+# a parsed SVG from Painter.load_svg, which draw_svg scales to any rect.
+# document is the backend's own object
+@dataclass(slots=True, frozen=True, eq=False)
+class SvgDocument:
+    document: Any
+    size: Size  # intrinsic; empty when the SVG doesn't give a width and height
+
+
 class Painter(Protocol):
     def clear_all(self) -> None:
         ...
@@ -180,15 +742,60 @@ class Painter(Protocol):
     def stroke_rect(self, rect: Rect) -> None:
         ...
 
+    def fill_inset_rect(self, rect: Rect, insets: EdgeInsets) -> None:
+        ...
+
+    def stroke_inset_rect(self, rect: Rect, insets: EdgeInsets) -> None:
+        ...
+
+    def stroke_outline(self, rect: Rect) -> None:
+        ...
+
     def fill_circle(self, circle: Circle) -> None:
         ...
 
     def stroke_circle(self, circle: Circle) -> None:
         ...
 
+    # strokes with style.stroke's color, and style.line's width and cap
+    def draw_line(self, start: Point, end: Point) -> None:
+        ...
+
+    # one connected stroke through points, styled like draw_line
+    def stroke_polyline(self, points: Sequence[Point]) -> None:
+        ...
+
+    def fill_polygon(self, points: Sequence[Point]) -> None:
+        ...
+
+    # close joins the last point back to the first
+    def stroke_polygon(self, points: Sequence[Point], close: bool = True) -> None:
+        ...
+
+    # angles are in degrees, clockwise from 3 o'clock. an open arc is stroked like
+    # draw_line, while pie slices and chords are filled with style.fill
+    def draw_arc(
+        self,
+        circle: Circle,
+        start_angle: float,
+        sweep_angle: float,
+        mode: ArcMode = ArcMode.OPEN,
+    ) -> None:
+        ...
+
+    def fill_path(self, path: Path) -> None:
+        ...
+
+    # styled like draw_line
+    def stroke_path(self, path: Path) -> None:
+        ...
+
     def translate(self, pos: Point) -> None:
         ...
 
+    def transform(self, transform: Transform) -> None:
+        ...
+
     def clip(self, rect: Rect) -> None:
         ...
 
@@ -204,10 +811,31 @@ class Painter(Protocol):
     def get_font_metrics(self) -> FontMetrics:
         ...
 
-    def draw_image(self, file_path: str, rect: Rect, use_cache: bool = True) -> None:
+    def draw_image(
+        self, file_path: FilePath, rect: Rect, use_cache: bool = True
+    ) -> None:
+        ...
+
+    def measure_image(self, file_path: FilePath, use_cache: bool = True) -> Size:
+        ...
+
+    def draw_image_tiled(
+        self,
+        file_path: FilePath,
+        rect: Rect,
+        tile_mode: TileMode = TileMode.REPEAT,
+        use_cache: bool = True,
+    ) -> None:
+        ...
+
+    # source is a file path or the SVG's bytes
+    def load_svg(self, source: FilePath | bytes) -> SvgDocument:
         ...
 
-    def measure_image(self, file_path: str, use_cache: bool = True) -> Size:
+    # a path is loaded first, and kept in the painter's cache with use_cache
+    def draw_svg(
+        self, svg: FilePath | SvgDocument, rect: Rect, use_cache: bool = True
+    ) -> None:
         ...
 
     def draw_net_image(self, url: str, rect: Rect, use_cache: bool = True) -> None:
@@ -219,6 +847,9 @@ class Painter(Protocol):
     def measure_np_array_as_an_image(self, array: np.ndarray) -> Size:
         ...
 
+    def get_image_async(self, name: str, file_path: FilePath, callback):
+        ...
+
     def get_net_image_async(self, name: str, url: str, callback):
         ...
 
@@ -234,18 +865,1021 @@ class Painter(Protocol):
     def draw_np_array_as_an_image_rect(self, array: np.ndarray, rect: Rect) -> None:
         ...
 
+    # buffer is anything writable through the buffer protocol, e.g. a bytearray,
+    # and receives the frame in the surface's own pixel format
+    def read_pixels_into(self, buffer) -> None:
+        ...
+
+    def to_numpy(self) -> np.ndarray:
+        ...
+
+    def save_image(
+        self,
+        file: FilePath | BinaryIO,
+        format: ImageFormat = ImageFormat.PNG,
+        quality: int = 90,
+    ) -> None:
+        ...
+
     def save(self) -> None:
         ...
 
     def restore(self) -> None:
         ...
 
+    # like save, but draws until the matching restore into an offscreen layer,
+    # which is composited through filter
+    def save_layer(
+        self, bounds: Optional[Rect] = None, filter: Optional[ImageFilter] = None
+    ) -> None:
+        ...
+
+    def save_count(self) -> int:
+        ...
+
+    def restore_to_count(self, count: int) -> None:
+        ...
+
+    def saved(self) -> ContextManager[None]:
+        ...
+
+    def clipped(self, rect: Rect) -> ContextManager[None]:
+        ...
+
+    def translated(self, pos: Point) -> ContextManager[None]:
+        ...
+
+    def draw_custom(self, bounds: Rect, callback: Callable[["Painter"], None]) -> None:
+        ...
+
     def style(self, style: Style) -> None:
         ...
 
     def flush(self) -> None:
         ...
 
+    def execute(self, commands: Iterable["BatchCommand"]) -> None:
+        ...
+
+
+# the Painter methods that only combine save, restore, clip and translate, shared
+# by the backends and the recorders. subclasses also provide save_count
+class PainterHelpers:
+    # undoes any saves above count, e.g. ones left unbalanced by a widget
+    def restore_to_count(self, count: int) -> None:
+        while self.save_count() > count:
+            self.restore()
+
+    @contextmanager
+    def saved(self) -> Iterator[None]:
+        self.save()
+        try:
+            yield
+        finally:
+            self.restore()
+
+    @contextmanager
+    def clipped(self, rect: Rect) -> Iterator[None]:
+        with self.saved():
+            self.clip(rect)
+            yield
+
+    @contextmanager
+    def translated(self, pos: Point) -> Iterator[None]:
+        with self.saved():
+            self.translate(pos)
+            yield
+
+    # callback draws in bounds-local coordinates, clipped to bounds
+    def draw_custom(self, bounds: Rect, callback: Callable[[Painter], None]) -> None:
+        with self.translated(bounds.origin):
+            self.clip(Rect(Point(0, 0), bounds.size))
+            callback(self)
+
+    def execute(self, commands: Iterable["BatchCommand"]) -> None:
+        execute_commands(self, commands)
+
+
+@dataclass(slots=True, frozen=True)
+class ClearAll:
+    pass
+
+
+@dataclass(slots=True, frozen=True)
+class FillRect:
+    rect: Rect
+
+
+@dataclass(slots=True, frozen=True)
+class StrokeRect:
+    rect: Rect
+
+
+@dataclass(slots=True, frozen=True)
+class FillInsetRect:
+    rect: Rect
+    insets: EdgeInsets
+
+
+@dataclass(slots=True, frozen=True)
+class StrokeInsetRect:
+    rect: Rect
+    insets: EdgeInsets
+
+
+@dataclass(slots=True, frozen=True)
+class StrokeOutline:
+    rect: Rect
+
+
+@dataclass(slots=True, frozen=True)
+class FillCircle:
+    circle: Circle
+
+
+@dataclass(slots=True, frozen=True)
+class StrokeCircle:
+    circle: Circle
+
+
+@dataclass(slots=True, frozen=True)
+class DrawLine:
+    start: Point
+    end: Point
+
+
+@dataclass(slots=True, frozen=True)
+class StrokePolyline:
+    points: tuple[Point, ...]
+
+
+@dataclass(slots=True, frozen=True)
+class FillPolygon:
+    points: tuple[Point, ...]
+
+
+@dataclass(slots=True, frozen=True)
+class StrokePolygon:
+    points: tuple[Point, ...]
+    close: bool = True
+
+
+@dataclass(slots=True, frozen=True)
+class DrawArc:
+    circle: Circle
+    start_angle: float
+    sweep_angle: float
+    mode: ArcMode = ArcMode.OPEN
+
+
+@dataclass(slots=True, frozen=True)
+class FillPath:
+    path: Path
+
+
+@dataclass(slots=True, frozen=True)
+class StrokePath:
+    path: Path
+
+
+@dataclass(slots=True, frozen=True)
+class Translate:
+    pos: Point
+
+
+@dataclass(slots=True, frozen=True)
+class Concat:
+    transform: Transform
+
+
+@dataclass(slots=True, frozen=True)
+class Clip:
+    rect: Rect
+
+
+@dataclass(slots=True, frozen=True)
+class FillText:
+    text: str
+    pos: Point
+    max_width: Optional[float] = None
+
+
+@dataclass(slots=True, frozen=True)
+class StrokeText:
+    text: str
+    pos: Point
+    max_width: Optional[float] = None
+
+
+@dataclass(slots=True, frozen=True)
+class DrawImage:
+    file_path: str
+    rect: Rect
+    use_cache: bool = True
+
+
+@dataclass(slots=True, frozen=True)
+class DrawImageTiled:
+    file_path: str
+    rect: Rect
+    tile_mode: TileMode = TileMode.REPEAT
+    use_cache: bool = True
+
+
+@dataclass(slots=True, frozen=True)
+class DrawSvg:
+    svg: str | SvgDocument
+    rect: Rect
+    use_cache: bool = True
+
+
+@dataclass(slots=True, frozen=True)
+class DrawNetImage:
+    url: str
+    rect: Rect
+    use_cache: bool = True
+
+
+# the live image commands hold backend images or arrays, so they compare by
+# identity and can't be serialized
+@dataclass(slots=True, frozen=True, eq=False)
+class DrawImageObject:
+    image: Any
+    x: float
+    y: float
+
+
+@dataclass(slots=True, frozen=True, eq=False)
+class DrawNpArray:
+    array: np.ndarray
+    x: float
+    y: float
+
+
+@dataclass(slots=True, frozen=True, eq=False)
+class DrawNpArrayRect:
+    array: np.ndarray
+    rect: Rect
+
+
+@dataclass(slots=True, frozen=True)
+class Save:
+    pass
+
+
+@dataclass(slots=True, frozen=True)
+class Restore:
+    pass
+
+
+@dataclass(slots=True, frozen=True)
+class SaveLayer:
+    bounds: Optional[Rect] = None
+    filter: Optional[ImageFilter] = None
+
+
+@dataclass(slots=True, frozen=True)
+class SetStyle:
+    style: Style
+
+
+DrawCommand: TypeAlias = Union[
+    ClearAll,
+    FillRect,
+    StrokeRect,
+    FillInsetRect,
+    StrokeInsetRect,
+    StrokeOutline,
+    FillCircle,
+    StrokeCircle,
+    DrawLine,
+    StrokePolyline,
+    FillPolygon,
+    StrokePolygon,
+    DrawArc,
+    FillPath,
+    StrokePath,
+    Translate,
+    Concat,
+    Clip,
+    FillText,
+    StrokeText,
+    DrawImage,
+    DrawImageTiled,
+    DrawSvg,
+    DrawNetImage,
+    DrawImageObject,
+    DrawNpArray,
+    DrawNpArrayRect,
+    Save,
+    Restore,
+    SaveLayer,
+    SetStyle,
+]
+
+_LIVE_COMMANDS = (DrawImageObject, DrawNpArray, DrawNpArrayRect)
+
+# each kind is also the name of the painter method that executes the command
+_KINDS: dict[type, str] = {
+    LinearGradient: "linear",
+    RadialGradient: "radial",
+    SweepGradient: "sweep",
+    BlurFilter: "blur",
+    DropShadowFilter: "drop_shadow",
+    ColorMatrixFilter: "color_matrix",
+    DisplacementFilter: "displacement",
+    BlendFilter: "blend",
+    ClearAll: "clear_all",
+    FillRect: "fill_rect",
+    StrokeRect: "stroke_rect",
+    FillInsetRect: "fill_inset_rect",
+    StrokeInsetRect: "stroke_inset_rect",
+    StrokeOutline: "stroke_outline",
+    FillCircle: "fill_circle",
+    StrokeCircle: "stroke_circle",
+    DrawLine: "draw_line",
+    StrokePolyline: "stroke_polyline",
+    FillPolygon: "fill_polygon",
+    StrokePolygon: "stroke_polygon",
+    DrawArc: "draw_arc",
+    FillPath: "fill_path",
+    StrokePath: "stroke_path",
+    MoveTo: "move_to",
+    LineTo: "line_to",
+    QuadTo: "quad_to",
+    CubicTo: "cubic_to",
+    ArcTo: "arc_to",
+    ClosePath: "close",
+    Translate: "translate",
+    Concat: "transform",
+    Clip: "clip",
+    FillText: "fill_text",
+    StrokeText: "stroke_text",
+    DrawImage: "draw_image",
+    DrawImageTiled: "draw_image_tiled",
+    DrawSvg: "draw_svg",
+    DrawNetImage: "draw_net_image",
+    DrawImageObject: "draw_image_object",
+    DrawNpArray: "draw_np_array_as_an_image",
+    DrawNpArrayRect: "draw_np_array_as_an_image_rect",
+    Save: "save",
+    Restore: "restore",
+    SaveLayer: "save_layer",
+    SetStyle: "style",
+}
+
+
+# commands may also be given as lightweight tuples such as ("fill_rect", rect)
+BatchCommand: TypeAlias = DrawCommand | tuple
+
+
+def execute_commands(painter: Painter, commands: Iterable[BatchCommand]) -> None:
+    for command in commands:
+        if isinstance(command, tuple):
+            command = _from_tuple(command)
+        method = getattr(painter, _KINDS[type(command)])
+        method(*(getattr(command, f.name) for f in fields(command)))
+
+
+_COMMANDS_BY_KIND: dict[str, type] = {
+    kind: cls for cls, kind in _KINDS.items() if cls in get_args(DrawCommand)
+}
+
+
+def _from_tuple(command: tuple) -> DrawCommand:
+    kind, *args = command
+    if kind not in _COMMANDS_BY_KIND:
+        raise ValueError(f"Unknown draw command: {kind!r}")
+    return _COMMANDS_BY_KIND[kind](*args)
+
+
+def commands_to_json(commands: Iterable[DrawCommand]) -> str:
+    return json.dumps([_to_jsonable(c) for c in commands])
+
+
+def commands_from_json(text: str) -> list[DrawCommand]:
+    return [_from_jsonable(DrawCommand, c) for c in json.loads(text)]
+
+
+class DisplayList(PainterHelpers):
+    def __init__(self, commands: Iterable[DrawCommand] = ()) -> None:
+        self._commands: list[DrawCommand] = list(commands)
+
+    def __iter__(self) -> Iterator[DrawCommand]:
+        return iter(self._commands)
+
+    def __len__(self) -> int:
+        return len(self._commands)
+
+    def __eq__(self, other: object) -> bool:
+        if not isinstance(other, DisplayList):
+            return NotImplemented
+        return self._commands == other._commands
+
+    @staticmethod
+    def from_json(text: str) -> "DisplayList":
+        return DisplayList(commands_from_json(text))
+
+    def to_json(self) -> str:
+        return commands_to_json(self._commands)
+
+    def add(self, command: DrawCommand):  # -> Self:
+        self._commands.append(command)
+        return self
+
+    def clear(self) -> None:
+        self._commands.clear()
+
+    def clear_all(self):  # -> Self:
+        return self.add(ClearAll())
+
+    def fill_rect(self, rect: Rect):  # -> Self:
+        return self.add(FillRect(rect))
+
+    def stroke_rect(self, rect: Rect):  # -> Self:
+        return self.add(StrokeRect(rect))
+
+    def fill_inset_rect(self, rect: Rect, insets: EdgeInsets):  # -> Self:
+        return self.add(FillInsetRect(rect, insets))
+
+    def stroke_inset_rect(self, rect: Rect, insets: EdgeInsets):  # -> Self:
+        return self.add(StrokeInsetRect(rect, insets))
+
+    def stroke_outline(self, rect: Rect):  # -> Self:
+        return self.add(StrokeOutline(rect))
+
+    def fill_circle(self, circle: Circle):  # -> Self:
+        return self.add(FillCircle(circle))
+
+    def stroke_circle(self, circle: Circle):  # -> Self:
+        return self.add(StrokeCircle(circle))
+
+    def draw_line(self, start: Point, end: Point):  # -> Self:
+        return self.add(DrawLine(start, end))
+
+    def stroke_polyline(self, points: Sequence[Point]):  # -> Self:
+        return self.add(StrokePolyline(tuple(points)))
+
+    def fill_polygon(self, points: Sequence[Point]):  # -> Self:
+        return self.add(FillPolygon(tuple(points)))
+
+    def stroke_polygon(
+        self, points: Sequence[Point], close: bool = True
+    ):  # -> Self:
+        return self.add(StrokePolygon(tuple(points), close))
+
+    def draw_arc(
+        self,
+        circle: Circle,
+        start_angle: float,
+        sweep_angle: float,
+        mode: ArcMode = ArcMode.OPEN,
+    ):  # -> Self:
+        return self.add(DrawArc(circle, start_angle, sweep_angle, mode))
+
+    # paths are copied, so building on one afterwards doesn't change the record
+    def fill_path(self, path: Path):  # -> Self:
+        return self.add(FillPath(Path(list(path.segments))))
+
+    def stroke_path(self, path: Path):  # -> Self:
+        return self.add(StrokePath(Path(list(path.segments))))
+
+    def translate(self, pos: Point):  # -> Self:
+        return self.add(Translate(pos))
+
+    def transform(self, transform: Transform):  # -> Self:
+        return self.add(Concat(transform))
+
+    def clip(self, rect: Rect):  # -> Self:
+        return self.add(Clip(rect))
+
+    def fill_text(
+        self, text: str, pos: Point, max_width: Optional[float] = None
+    ):  # -> Self:
+        return self.add(FillText(text, pos, max_width))
+
+    def stroke_text(
+        self, text: str, pos: Point, max_width: Optional[float] = None
+    ):  # -> Self:
+        return self.add(StrokeText(text, pos, max_width))
+
+    def draw_image(
+        self, file_path: FilePath, rect: Rect, use_cache: bool = True
+    ):  # -> Self:
+        return self.add(DrawImage(as_path_str(file_path), rect, use_cache))
+
+    def draw_image_tiled(
+        self,
+        file_path: FilePath,
+        rect: Rect,
+        tile_mode: TileMode = TileMode.REPEAT,
+        use_cache: bool = True,
+    ):  # -> Self:
+        return self.add(
+            DrawImageTiled(as_path_str(file_path), rect, tile_mode, use_cache)
+        )
+
+    def draw_svg(
+        self, svg: FilePath | SvgDocument, rect: Rect, use_cache: bool = True
+    ):  # -> Self:
+        if not isinstance(svg, SvgDocument):
+            svg = as_path_str(svg)
+        return self.add(DrawSvg(svg, rect, use_cache))
+
+    def draw_net_image(
+        self, url: str, rect: Rect, use_cache: bool = True
+    ):  # -> Self:
+        return self.add(DrawNetImage(url, rect, use_cache))
+
+    def draw_image_object(self, img, x: float, y: float):  # -> Self:
+        return self.add(DrawImageObject(img, x, y))
+
+    def draw_np_array_as_an_image(
+        self, array: np.ndarray, x: float, y: float
+    ):  # -> Self:
+        return self.add(DrawNpArray(array, x, y))
+
+    def draw_np_array_as_an_image_rect(
+        self, array: np.ndarray, rect: Rect
+    ):  # -> Self:
+        return self.add(DrawNpArrayRect(array, rect))
+
+    def save(self):  # -> Self:
+        return self.add(Save())
+
+    def restore(self):  # -> Self:
+        return self.add(Restore())
+
+    def save_layer(
+        self, bounds: Optional[Rect] = None, filter: Optional[ImageFilter] = None
+    ):  # -> Self:
+        return self.add(SaveLayer(bounds, filter))
+
+    # saves in the list that no restore has matched yet
+    def save_count(self) -> int:
+        depth = 0
+        for c in self._commands:
+            if isinstance(c, (Save, SaveLayer)):
+                depth += 1
+            elif isinstance(c, Restore) and depth > 0:
+                depth -= 1
+        return depth
+
+    def restore_to_count(self, count: int):  # -> Self:
+        super().restore_to_count(count)
+        return self
+
+    # the callback's drawing is recorded in place
+    def draw_custom(
+        self, bounds: Rect, callback: Callable[[Painter], None]
+    ):  # -> Self:
+        super().draw_custom(bounds, callback)
+        return self
+
+    def style(self, style: Style):  # -> Self:
+        return self.add(SetStyle(style))
+
+
+# records drawing calls for a later Painter.execute; measurement still needs a
+# real painter, since text and image sizes depend on the backend
+class RecordingPainter(DisplayList):
+    def __init__(self, painter: Optional[Painter] = None) -> None:
+        super().__init__()
+        self._painter = painter
+        self._style: Optional[Style] = None
+        self._style_stack: list[Optional[Style]] = []
+
+    def finish(self) -> DisplayList:
+        display_list = DisplayList(self)
+        self.clear()
+        return display_list
+
+    def save(self):  # -> Self:
+        self._style_stack.append(self._style)
+        return super().save()
+
+    def save_layer(
+        self, bounds: Optional[Rect] = None, filter: Optional[ImageFilter] = None
+    ):  # -> Self:
+        self._style_stack.append(self._style)
+        return super().save_layer(bounds, filter)
+
+    def restore(self):  # -> Self:
+        self._style = self._style_stack.pop()
+        return super().restore()
+
+    def save_count(self) -> int:
+        return len(self._style_stack)
+
+    def style(self, style: Style):  # -> Self:
+        self._style = style
+        return super().style(style)
+
+    def measure_text(self, text: str) -> float:
+        return self._measure_with_style(lambda p: p.measure_text(text))
+
+    def get_font_metrics(self) -> FontMetrics:
+        return self._measure_with_style(lambda p: p.get_font_metrics())
+
+    def measure_image(self, file_path: FilePath, use_cache: bool = True) -> Size:
+        return self._get_painter().measure_image(file_path, use_cache)
+
+    def measure_net_image(self, url: str, use_cache: bool = True) -> Size:
+        return self._get_painter().measure_net_image(url, use_cache)
+
+    def measure_np_array_as_an_image(self, array: np.ndarray) -> Size:
+        return self._get_painter().measure_np_array_as_an_image(array)
+
+    def load_svg(self, source: FilePath | bytes) -> SvgDocument:
+        return self._get_painter().load_svg(source)
+
+    # loading is left to the painter; the image it returns is recorded when drawn
+    def get_image_async(self, name: str, file_path: FilePath, callback):
+        return self._get_painter().get_image_async(name, file_path, callback)
+
+    def get_net_image_async(self, name: str, url: str, callback):
+        return self._get_painter().get_net_image_async(name, url, callback)
+
+    def get_numpy_image_async(self, array: np.ndarray, callback):
+        return self._get_painter().get_numpy_image_async(array, callback)
+
+    def read_pixels_into(self, buffer) -> None:
+        raise RuntimeError("RecordingPainter has no pixels; execute the recording")
+
+    def to_numpy(self) -> np.ndarray:
+        raise RuntimeError("RecordingPainter has no pixels; execute the recording")
+
+    def save_image(
+        self,
+        file: FilePath | BinaryIO,
+        format: ImageFormat = ImageFormat.PNG,
+        quality: int = 90,
+    ) -> None:
+        raise RuntimeError("RecordingPainter has no pixels; execute the recording")
+
+    # the recording is kept until finish()
+    def flush(self) -> None:
+        pass
+
+    def _get_painter(self) -> Painter:
+        if self._painter is None:
+            raise RuntimeError("RecordingPainter has no painter to measure with")
+        return self._painter
+
+    # text measurement depends on the recorded style, so apply it temporarily
+    def _measure_with_style(self, measure: Callable[[Painter], Any]) -> Any:
+        painter = self._get_painter()
+        painter.save()
+        try:
+            if self._style is not None:
+                painter.style(self._style)
+            return measure(painter)
+        finally:
+            painter.restore()
+
+
+# coordinates beyond this are treated as a layout bug rather than a real position
+MAX_COORDINATE = 1e7
+
+
+# wraps a painter and rejects NaN, infinite or absurdly large geometry before it
+# reaches the backend, naming the call that received it. enabled by setting the
+# CASTELLA_VALIDATE_PAINTER environment variable; see validating()
+class ValidatingPainter:
+    def __init__(self, painter: Painter) -> None:
+        self._painter = painter
+
+    def __getattr__(self, name: str) -> Any:
+        attr = getattr(self._painter, name)
+        if not callable(attr):
+            return attr
+
+        def validated(*args, **kwargs):
+            for arg in (*args, *kwargs.values()):
+                _validate_geometry(name, arg, arg)
+            return attr(*args, **kwargs)
+
+        return validated
+
+    def draw_custom(self, bounds: Rect, callback: Callable[[Painter], None]) -> None:
+        _validate_geometry("draw_custom", bounds, bounds)
+        self._painter.draw_custom(bounds, lambda _: callback(self))
+
+    def execute(self, commands: Iterable["BatchCommand"]) -> None:
+        execute_commands(self, commands)
+
+
+def validating(painter: Painter) -> Painter:
+    if os.environ.get("CASTELLA_VALIDATE_PAINTER"):
+        return ValidatingPainter(painter)
+    return painter
+
+
+_GEOMETRY_TYPES = (
+    Point,
+    Size,
+    Rect,
+    Circle,
+    EdgeInsets,
+    Transform,
+    Path,
+    *get_args(PathSegment),
+)
+
+
+def _validate_geometry(name: str, arg: Any, value: Any) -> None:
+    if isinstance(value, _GEOMETRY_TYPES):
+        for f in fields(value):
+            _validate_geometry(name, arg, getattr(value, f.name))
+    elif isinstance(value, (list, tuple)):
+        for v in value:
+            _validate_geometry(name, arg, v)
+    elif isinstance(value, (int, float)) and not isinstance(value, bool):
+        if not math.isfinite(value):
+            raise ValueError(f"{name}() got a non-finite value in {arg!r}")
+        if abs(value) > MAX_COORDINATE:
+            raise ValueError(f"{name}() got an out-of-range value in {arg!r}")
+
+
+class DebugOverlay(Flag):
+    BOUNDS = auto()  # outlines every clip, which is where each widget draws
+    REPAINTS = auto()  # flashes what each flush repainted, in a rotating color
+    OVERDRAW = auto()  # tints painted regions, more strongly where painted more
+
+
+_REPAINT_COLORS = ("#f4433640", "#4caf5040", "#2196f340")
+
+
+# draws diagnostics over a painter's output. drawing is recorded and replayed at
+# flush, when what the frame painted is known. backend calls outside the
+# protocol are only forwarded when they can't paint, like begin_frame
+class DebugOverlayPainter(RecordingPainter):
+    def __init__(self, painter: Painter, overlay: DebugOverlay) -> None:
+        super().__init__(painter)
+        self._overlay = overlay
+        # kept between flushes, since the frames flush inside a translated()
+        self._tracer = _Tracer()
+        self._flushes = 0
+
+    def __getattr__(self, name: str) -> Any:
+        if name == "_painter":
+            raise AttributeError(name)
+        if name == "begin_frame" or name.startswith(("measure_", "get_")):
+            return getattr(self._painter, name)
+        # drawing straight to the painter would land outside the recorded order,
+        # transform and clip
+        raise AttributeError(
+            f"DebugOverlayPainter can't forward {name!r}, which may paint"
+        )
+
+    # the frame is read back as flushed, overlay included
+    def read_pixels_into(self, buffer) -> None:
+        self._get_painter().read_pixels_into(buffer)
+
+    def flush(self) -> None:
+        painter = self._get_painter()
+        clips = []
+        painted = []
+        for command in self:
+            region = self._tracer.feed(command)
+            if isinstance(command, Clip):
+                clips.append(self._tracer.clip)
+            if region is not None:
+                painted.append(region[1].intersection(self._tracer.clip))
+        painter.execute(self)
+        self.clear()
+
+        # the overlay is drawn in frame coordinates, which a flush under a
+        # degenerate transform, e.g. scale(0), can't get back to; that frame is
+        # presented without it
+        try:
+            inverse = self._tracer.transform.invert()
+        except ValueError:
+            inverse = None
+        if inverse is not None:
+            painter.save()
+            painter.transform(inverse)
+            self._draw_overlay(painter, clips, [r for r in painted if r is not None])
+            painter.restore()
+        self._flushes += 1
+        painter.flush()
+
+    def _draw_overlay(
+        self, painter: Painter, clips: list[Rect], painted: list[Rect]
+    ) -> None:
+        if DebugOverlay.OVERDRAW in self._overlay:
+            painter.style(Style(fill=FillStyle(color="#ff000018")))
+            for rect in painted:
+                painter.fill_rect(rect)
+        if DebugOverlay.REPAINTS in self._overlay and painted:
+            color = _REPAINT_COLORS[self._flushes % len(_REPAINT_COLORS)]
+            painter.style(Style(fill=FillStyle(color=color)))
+            bounds = painted[0]
+            for rect in painted[1:]:
+                bounds = bounds.union(rect)
+            painter.fill_rect(bounds)
+        if DebugOverlay.BOUNDS in self._overlay:
+            painter.style(Style(stroke=StrokeStyle(color="#00bcd4")))
+            for rect in clips:
+                painter.stroke_rect(rect)
+
+
+# CASTELLA_DEBUG_OVERLAY takes a comma separated list of DebugOverlay names,
+# e.g. "bounds,repaints"
+def instrument(painter: Painter) -> Painter:
+    names = os.environ.get("CASTELLA_DEBUG_OVERLAY")
+    if names:
+        overlay = DebugOverlay(0)
+        for name in names.split(","):
+            name = name.strip().upper()
+            if name not in DebugOverlay.__members__:
+                expected = ", ".join(DebugOverlay.__members__)
+                raise ValueError(
+                    f"Invalid DebugOverlay: {name!r}, expected one of {expected}"
+                )
+            overlay |= DebugOverlay[name]
+        painter = DebugOverlayPainter(painter, overlay)
+    return validating(painter)
+
+
+# regions of the frame that differ between two recordings of it, in frame
+# coordinates. commands are matched as a sequence, so inserting one doesn't mark
+# everything after it as changed.
+def diff_display_lists(
+    old: Iterable[DrawCommand], new: Iterable[DrawCommand]
+) -> list[Rect]:
+    old_painted = _painted_regions(old)
+    new_painted = _painted_regions(new)
+    matcher = difflib.SequenceMatcher(
+        None, [key for key, _ in old_painted], [key for key, _ in new_painted], False
+    )
+    regions = []
+    for tag, i1, i2, j1, j2 in matcher.get_opcodes():
+        if tag != "equal":
+            regions.extend(bounds for _, bounds in old_painted[i1:i2])
+            regions.extend(bounds for _, bounds in new_painted[j1:j2])
+    return regions
+
+
+# stands in for "everywhere", since Rect can't hold infinite coordinates
+_EXTENT = 1e9
+_EVERYWHERE = Rect.from_ltrb(-_EXTENT, -_EXTENT, _EXTENT, _EXTENT)
+
+
+# pairs each drawing command, keyed together with the state it's drawn in, with
+# the frame region it can touch
+def _painted_regions(commands: Iterable[DrawCommand]) -> list[tuple[str, Rect]]:
+    tracer = _Tracer()
+    return [p for p in map(tracer.feed, commands) if p is not None]
+
+
+# follows the transform, clip and style a sequence of commands is drawn with
+class _Tracer:
+    def __init__(self) -> None:
+        self.transform = Transform()
+        self.clip = _EVERYWHERE
+        self.style = Style()
+        # a filtered layer may spread its contents anywhere within it, e.g. by a
+        # blur
+        self._layer: Optional[Rect] = None
+        self._stack: list[tuple[Transform, Rect, Style, Optional[Rect]]] = []
+
+    # the key and frame region of what command paints, if anything
+    def feed(self, command: DrawCommand) -> Optional[tuple[str, Rect]]:
+        match command:
+            case Save():
+                self._push()
+            case SaveLayer(filter=f):
+                self._push()
+                # the filtered result is only clipped by the clip outside it
+                if f is not None and self._layer is None:
+                    self._layer = self.clip
+                    return self._key(command), self.clip
+            case Restore():
+                # a recording can start inside a save made before it, e.g. by
+                # the frames' translated(); the state before that is unknown,
+                # so an unmatched restore keeps the current one
+                if self._stack:
+                    (self.transform, self.clip, self.style, self._layer) = (
+                        self._stack.pop()
+                    )
+            case SetStyle(style=new_style):
+                self.style = new_style
+            case Translate(pos=pos):
+                self.transform = self.transform.multiply(
+                    Transform.translate(pos.x, pos.y)
+                )
+            case Concat(transform=t):
+                self.transform = self.transform.multiply(t)
+            case Clip(rect=rect):
+                # the painters clip to the rect's size at the current origin
+                local = Rect(Point(0, 0), rect.size + Size(1, 1))
+                self.clip = self.clip.intersection(
+                    _map_rect(self.transform, local)
+                ) or Rect(Point(0, 0), Size(0, 0))
+            case ClearAll():
+                return self._key(command), _EVERYWHERE
+            case _:
+                if self._layer is not None:
+                    return self._key(command), self._layer
+                bounds = _map_rect(self.transform, _local_bounds(command, self.style))
+                bounds = bounds.intersection(self.clip)
+                if bounds is not None:
+                    return self._key(command), bounds
+        return None
+
+    def _push(self) -> None:
+        self._stack.append((self.transform, self.clip, self.style, self._layer))
+
+    def _key(self, command: DrawCommand) -> str:
+        return _to_key(command, self.transform, self.clip, self.style)
+
+
+def _to_key(
+    command: DrawCommand, transform: Transform, clip: Rect, style: Style
+) -> str:
+    if isinstance(command, _LIVE_COMMANDS) or (
+        isinstance(command, DrawSvg) and isinstance(command.svg, SvgDocument)
+    ):
+        # a live image is only the same as itself
+        return json.dumps(
+            [_KINDS[type(command)], id(command), _to_jsonable((transform, clip, style))]
+        )
+    return json.dumps(_to_jsonable((command, transform, clip, style)))
+
+
+def _local_bounds(command: DrawCommand, style: Style) -> Rect:
+    # widened by the stroke width, or a pixel for anti-aliasing
+    margin = max(style.line.width, 1)
+    match command:
+        case FillText(pos=pos, max_width=w) | StrokeText(pos=pos, max_width=w):
+            # pos is on the baseline; without max_width the text may reach the clip
+            right = _EXTENT if w is None else pos.x + w
+            size = style.font.size
+            return Rect.from_ltrb(pos.x, pos.y - size, right, pos.y + size / 2)
+        case FillCircle(circle=c) | StrokeCircle(circle=c) | DrawArc(circle=c):
+            r = c.radius
+            return Rect.from_ltrb(
+                c.center.x - r, c.center.y - r, c.center.x + r, c.center.y + r
+            ).inflate(margin)
+        case (
+            FillInsetRect(rect=rect, insets=insets)
+            | StrokeInsetRect(rect=rect, insets=insets)
+        ):
+            return rect.inset(insets).inflate(margin)
+        case StrokeOutline(rect=rect) if style.outline is not None:
+            # the outline lies outside rect, past its offset
+            return rect.inflate(style.outline.offset + style.outline.width + 1)
+        case DrawLine(start=start, end=end):
+            return _points_bounds((start, end)).inflate(margin)
+        case (
+            StrokePolyline(points=points)
+            | FillPolygon(points=points)
+            | StrokePolygon(points=points)
+        ):
+            return _points_bounds(points).inflate(margin)
+        case DrawImageObject(image=image, x=x, y=y):
+            # both backends' images report their size this way
+            return Rect(Point(x, y), Size(image.width(), image.height()))
+        case DrawNpArray(array=array, x=x, y=y):
+            height, width = array.shape[:2]
+            return Rect(Point(x, y), Size(width, height))
+        case FillPath(path=path) | StrokePath(path=path):
+            # a tangent arc's ends can lie far past its points, so don't bound it
+            if any(isinstance(segment, ArcTo) for segment in path.segments):
+                return _EVERYWHERE
+            # curves stay within their control points
+            points = [
+                getattr(segment, f.name)
+                for segment in path.segments
+                for f in fields(segment)
+            ]
+            return _points_bounds(points).inflate(margin)
+        case _:
+            # every other drawing command has a rect
+            return command.rect.inflate(margin)
+
+
+def _points_bounds(points: Sequence[Point]) -> Rect:
+    if not points:
+        return Rect(Point(0, 0), Size(0, 0))
+    xs = [p.x for p in points]
+    ys = [p.y for p in points]
+    return Rect.from_ltrb(min(xs), min(ys), max(xs), max(ys))
+
+
+def _map_rect(transform: Transform, rect: Rect) -> Rect:
+    left, top, right, bottom = rect.to_ltrb()
+    points = [
+        transform.map_point(Point(x, y)) for x in (left, right) for y in (top, bottom)
+    ]
+    return Rect.from_ltrb(
+        min(p.x for p in points),
+        min(p.y for p in points),
+        max(p.x for p in points),
+        max(p.y for p in points),
+    )
+
 
 W = TypeVar("W", bound="Widget")
 
@@ -437,6 +2071,7 @@ class Widget(ABC):
         self._dirty = True
         self._enable_to_detach = True
         self._parent = None
+        self._named_style: Optional[str] = None
         self._widget_styles = get_theme().get_widget_styles(self)
         self._on_update_widget_styles()
 
@@ -464,7 +2099,7 @@ class Widget(ABC):
         self._on_update_widget_styles()
         return self
 
-    def bg_color(self, rgb: str):  # -> Self:
+    def bg_color(self, rgb: str | Color):  # -> Self:
         return self.change_style(
             Kind.NORMAL,
             AppearanceState.NORMAL,
@@ -474,7 +2109,7 @@ class Widget(ABC):
             ),
         )
 
-    def text_color(self, rgb: str):  # -> Self:
+    def text_color(self, rgb: str | Color):  # -> Self:
         return self.change_style(
             Kind.NORMAL,
             AppearanceState.NORMAL,
@@ -484,10 +2119,10 @@ class Widget(ABC):
             ),
         )
 
-    def fg_color(self, rgb: str):  # -> Self:
+    def fg_color(self, rgb: str | Color):  # -> Self:
         return self.text_color(rgb)
 
-    def border_color(self, rgb: str):  # -> Self:
+    def border_color(self, rgb: str | Color):  # -> Self:
         return self.change_style(
             Kind.NORMAL,
             AppearanceState.NORMAL,
@@ -502,6 +2137,18 @@ class Widget(ABC):
             self._get_widget_style(Kind.NORMAL, AppearanceState.NORMAL).bg_color
         )
 
+    # draws the widget with a style from the active StyleSheet instead of its
+    # theme style; see _named_painter_styles. None goes back to the theme
+    def named_style(self, name: Optional[str]):  # -> Self:
+        self._named_style = name
+        if name is None:
+            _named_style_widgets.discard(self)
+        else:
+            _named_style_widgets.add(self)
+        self._on_update_widget_styles()
+        self._dirty = True
+        return self
+
     def _on_update_widget_styles(self) -> None:
         pass
 
@@ -523,9 +2170,12 @@ class Widget(ABC):
     def _get_painter_styles(
         self, kind: "Kind", appearance_state: "AppearanceState"
     ) -> tuple[Style, Style]:
-        return Widget._convert_widget_style_to_painter_styles(
+        styles = Widget._convert_widget_style_to_painter_styles(
             self._get_widget_style(kind, appearance_state)
         )
+        if self._named_style is None:
+            return styles
+        return _named_painter_styles(self._named_style, appearance_state, styles)
 
     def dispatch(self, p: Point) -> tuple[Optional["Widget"], Point | None]:
         if self.contain(p):
@@ -541,9 +2191,7 @@ class Widget(ABC):
         return None, None
 
     def contain(self, p: Point) -> bool:
-        return (self._pos.x < p.x < self._pos.x + self._size.width) and (
-            self._pos.y < p.y < self._pos.y + self._size.height
-        )
+        return Rect(self._pos, self._size).contain_strictly(p)
 
     def on_attach(self, o: Observable) -> None:
         self._observable.append(o)
@@ -593,6 +2241,12 @@ class Widget(ABC):
     def unfocused(self) -> None:
         pass
 
+    # an outline the parent layout draws around the widget, e.g. a focus ring.
+    # the parent draws it, since the widget's own drawing is clipped to its
+    # bounds
+    def focus_outline(self) -> Optional[OutlineStyle]:
+        return None
+
     @abstractmethod
     def redraw(self, p: Painter, completely: bool) -> None:
         ...
@@ -898,9 +2552,9 @@ def _get_color_schema() -> dict[str, str]:
 
 @dataclass(slots=True, frozen=True)
 class WidgetStyle:
-    bg_color: str = "#000000"
-    border_color: str = "#FFFFFF"
-    text_color: str = "#FFFFFF"
+    bg_color: str | Color = "#000000"
+    border_color: str | Color = "#FFFFFF"
+    text_color: str | Color = "#FFFFFF"
     text_font: Font = Font()
 
 
@@ -1083,6 +2737,112 @@ def set_theme(theme: Theme) -> None:
     _theme = theme
 
 
+# "button.primary" inherits from "button" when the latter is registered
+class StyleSheet:
+    def __init__(self, styles: Optional[dict[str, Style | dict]] = None) -> None:
+        self._styles: dict[str, Style | dict] = {}
+        self._resolved: dict[str, Style] = {}
+        for name, style in (styles or {}).items():
+            self.register(name, style)
+
+    @staticmethod
+    def from_json(text: str) -> "StyleSheet":
+        return StyleSheet(json.loads(text))
+
+    def register(self, name: str, style: Style | dict):  # -> Self:
+        # a dict only overrides the given keys of the parent style
+        self._styles[name] = style
+        self._resolved.clear()
+        if self is _style_sheet:
+            _restyle_named_widgets()
+        return self
+
+    def __contains__(self, name: str) -> bool:
+        return name in self._styles
+
+    def resolve(self, name: str) -> Style:
+        if name in self._resolved:
+            return self._resolved[name]
+        if name not in self._styles:
+            raise RuntimeError(f"Unknown style: {name}")
+
+        style = self._styles[name]
+        if not isinstance(style, Style):
+            style = self._resolve_parent(name).merged(style)
+        self._resolved[name] = style
+        return style
+
+    def _resolve_parent(self, name: str) -> Style:
+        while "." in name:
+            name = name.rpartition(".")[0]
+            if name in self._styles:
+                return self.resolve(name)
+        return Style()
+
+
+def _merge_dicts(base: dict, override: dict) -> dict:
+    merged = dict(base)
+    for key, value in override.items():
+        old = merged.get(key)
+        # a value of another kind, e.g. a radial gradient over a linear one,
+        # shares no fields with the old one, so it replaces it whole
+        if (
+            isinstance(value, dict)
+            and isinstance(old, dict)
+            and value.get("kind") == old.get("kind")
+        ):
+            merged[key] = _merge_dicts(old, value)
+        else:
+            merged[key] = value
+    return merged
+
+
+_style_sheet = StyleSheet()
+
+
+def get_style_sheet() -> StyleSheet:
+    return _style_sheet
+
+
+def set_style_sheet(style_sheet: StyleSheet) -> None:
+    global _style_sheet
+    _style_sheet = style_sheet
+    _restyle_named_widgets()
+
+
+_named_style_widgets: "weakref.WeakSet[Widget]" = weakref.WeakSet()
+
+
+# a named style draws the widget's box and "<name>.text" its text, each falling
+# back to the theme when not registered. other states than NORMAL look for e.g.
+# "<name>.hover" and "<name>.hover.text" first
+def _named_painter_styles(
+    name: str, state: AppearanceState, theme_styles: tuple[Style, Style]
+) -> tuple[Style, Style]:
+    sheet = get_style_sheet()
+    names = [name]
+    if state is not AppearanceState.NORMAL:
+        names.insert(0, f"{name}.{state.name.lower()}")
+    box, text = theme_styles
+    box = next((sheet.resolve(n) for n in names if n in sheet), box)
+    text = next(
+        (sheet.resolve(f"{n}.text") for n in names if f"{n}.text" in sheet), text
+    )
+    return box, text
+
+
+# widgets keep the painter styles they worked out, so a changed style sheet has
+# to restyle them and repaint
+def _restyle_named_widgets() -> None:
+    widgets = list(_named_style_widgets)
+    for widget in widgets:
+        widget._on_update_widget_styles()
+        widget.dirty(True)
+    if widgets and hasattr(App, "_instance"):
+        app = App.get()
+        app._frame.post_update(UpdateEvent(app, True))
+
+
 def replace_font_size(style: Style, size: float, policy: FontSizePolicy) -> Style:
     return replace(style, font=replace(style.font, size=size, size_policy=policy))
 
@@ -1237,11 +2997,12 @@ class App:
             pos = self._layerPositions[i]
             self._relocate_layout(l, pos)
             if completely or l.is_dirty():
+                depth = p.save_count()
                 p.save()
                 p.translate(l.get_pos())
                 p.clip(Rect(Point(0, 0), l.get_size()))
                 l.redraw(p, completely)
-                p.restore()
+                p.restore_to_count(depth)
                 l.dirty(False)
         p.flush()
 
@@ -1375,9 +3136,7 @@ class Layout(Widget, ABC):
         return p + Point(0, 0)
 
     def contain_in_content_area(self, p: Point) -> bool:
-        return (self._pos.x < p.x < self._pos.x + self._size.width) and (
-            self._pos.y < p.y < self._pos.y + self._size.height
-        )
+        return Rect(self._pos, self._size).contain_strictly(p)
 
     def redraw(self, p: Painter, completely: bool) -> None:
         p.style(self._style)
@@ -1395,6 +3154,10 @@ class Layout(Widget, ABC):
             if completely or c.is_dirty():
                 p.save()
                 p.translate((c.get_pos() - self.get_pos()))
+                outline = c.focus_outline()
+                if outline is not None:
+                    p.style(Style(outline=outline))
+                    p.stroke_outline(Rect(Point(0, 0), c.get_size()))
                 p.clip(Rect(Point(0, 0), c.get_size()))
                 c.redraw(p, completely)
                 p.restore()
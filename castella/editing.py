@@ -0,0 +1,95 @@
+import re
+
+from castella.core import Painter, Point, Rect, Size
+
+# geometry and navigation for single-style text fields. positions are baselines
+# as passed to Painter.fill_text, and the painter must already have the text's
+# style applied, since every width comes from measure_text.
+
+_WORD = re.compile(r"\w+")
+
+
+def caret_rect(
+    p: Painter, text: str, index: int, pos: Point, font_size: float, width: float = 1
+) -> Rect:
+    return Rect(
+        Point(pos.x + p.measure_text(text[:index]), _line_top(p, pos, font_size)),
+        Size(width, font_size),
+    )
+
+
+def selection_rect(
+    p: Painter, text: str, start: int, end: int, pos: Point, font_size: float
+) -> Rect:
+    start, end = sorted((start, end))
+    left = p.measure_text(text[:start])
+    right = p.measure_text(text[:end])
+    return Rect(
+        Point(pos.x + left, _line_top(p, pos, font_size)),
+        Size(right - left, font_size),
+    )
+
+
+# the caret index closest to x, for placing the caret under a click
+def index_at(p: Painter, text: str, pos: Point, x: float) -> int:
+    x -= pos.x
+    prev = 0.0
+    for i in range(1, len(text) + 1):
+        width = p.measure_text(text[:i])
+        if x < (prev + width) / 2:
+            return i - 1
+        prev = width
+    return len(text)
+
+
+# underlines the not yet committed part of an IME composition, using the fill
+# of the current style
+def draw_composition_underline(
+    p: Painter, text: str, start: int, end: int, pos: Point, thickness: float = 1
+) -> None:
+    left = p.measure_text(text[:start])
+    right = p.measure_text(text[:end])
+    p.fill_rect(
+        Rect(Point(pos.x + left, pos.y + thickness), Size(right - left, thickness))
+    )
+
+
+def prev_word_boundary(text: str, index: int) -> int:
+    starts = [m.start() for m in _WORD.finditer(text, 0, index)]
+    return starts[-1] if starts else 0
+
+
+def next_word_boundary(text: str, index: int) -> int:
+    m = _WORD.search(text, index)
+    return len(text) if m is None else m.end()
+
+
+def line_start(text: str, index: int) -> int:
+    return text.rfind("\n", 0, index) + 1
+
+
+def line_end(text: str, index: int) -> int:
+    end = text.find("\n", index)
+    return len(text) if end == -1 else end
+
+
+# the smallest change to a scroll offset that brings target fully into a
+# viewport of the given size; target is in content coordinates
+def scroll_to_reveal(target: Rect, offset: Point, viewport: Size) -> Point:
+    return Point(
+        _reveal(target.origin.x, target.size.width, offset.x, viewport.width),
+        _reveal(target.origin.y, target.size.height, offset.y, viewport.height),
+    )
+
+
+def _reveal(start: float, length: float, offset: float, visible: float) -> float:
+    if start < offset:
+        return start
+    if start + length > offset + visible:
+        return start + length - visible
+    return offset
+
+
+def _line_top(p: Painter, pos: Point, font_size: float) -> float:
+    cap_height = p.get_font_metrics().cap_height
+    return pos.y - cap_height - (font_size - cap_height) / 2
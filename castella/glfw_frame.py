@@ -62,7 +62,7 @@ class Frame:
             skia.ColorSpace.MakeSRGB(),
         )
         self.surface = surface
-        self.painter = painter.Painter(self, self.surface)
+        self.painter = core.instrument(painter.Painter(self, self.surface))
 
     def mouse_button(self, window, button, action, mods):
         if button != glfw.MOUSE_BUTTON_LEFT:
@@ -116,17 +116,33 @@ class Frame:
         self._callback_on_input_key = handler
 
     def on_redraw(self, handler: Callable[[core.Painter, bool], None]) -> None:
-        callback = lambda window, w, h: self._on_redraw(window, w, h, handler)
-        self._on_load = callback
+        self._on_load = lambda window, w, h: self._on_redraw(window, w, h, handler)
+        callback = lambda window, w, h: self._on_resize(window, handler)
         glfw.set_window_size_callback(self.window, callback)
+        # fullscreen toggles and moves between monitors with different scaling
+        # can recreate the framebuffer without changing the window size
+        glfw.set_framebuffer_size_callback(self.window, callback)
+
+    # a resize usually fires both callbacks, in an order that depends on the
+    # platform, so only the first one to see the change redraws
+    def _on_resize(self, window, handler: Callable[[core.Painter, bool], None]) -> None:
+        size = core.Size(*glfw.get_window_size(window))
+        if size != self._size or self._framebuffer_changed():
+            self._on_redraw(window, size.width, size.height, handler)
 
     def _on_redraw(
         self, window, w, h, handler: Callable[[core.Painter, bool], None]
     ) -> None:
         self._size = core.Size(w, h)
-        self._update_surface_and_painter()
+        if self._framebuffer_changed():
+            self._update_surface_and_painter()
+        self.painter.begin_frame()
         handler(self.painter, True)
 
+    def _framebuffer_changed(self) -> bool:
+        surface_size = (self.surface.width(), self.surface.height())
+        return tuple(glfw.get_framebuffer_size(self.window)) != surface_size
+
     def get_painter(self) -> core.Painter:
         return self.painter
 
@@ -152,15 +168,16 @@ class Frame:
             pos = w.get_pos()
             clippedRect = core.Rect(core.Point(0, 0), w.get_size())
 
-        self.painter.save()
-        try:
-            self.painter.translate(pos)
-            if clippedRect is not None:
-                self.painter.clip(clippedRect)
-            ev.target.redraw(self.painter, ev.completely)
+        self.painter.begin_frame()
+        with self.painter.translated(pos):
+            depth = self.painter.save_count()
+            try:
+                if clippedRect is not None:
+                    self.painter.clip(clippedRect)
+                ev.target.redraw(self.painter, ev.completely)
+            finally:
+                self.painter.restore_to_count(depth)
             self.painter.flush()
-        finally:
-            self.painter.restore()
 
     def flush(self) -> None:
         GL.glFlush()
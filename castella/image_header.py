@@ -0,0 +1,195 @@
+import io
+import os
+import struct
+from dataclasses import dataclass
+from typing import BinaryIO, Optional
+
+
+# width and height are as displayed, after any EXIF orientation, which is how
+# skia decodes the image
+@dataclass(slots=True, frozen=True)
+class ImageHeader:
+    format: str
+    width: int
+    height: int
+    orientation: int = 1  # the EXIF orientation, 1 to 8
+
+
+def probe(file_path: str | os.PathLike[str]) -> Optional[ImageHeader]:
+    with open(file_path, "rb") as f:
+        return _probe(f)
+
+
+def probe_bytes(data: bytes) -> Optional[ImageHeader]:
+    return _probe(io.BytesIO(data))
+
+
+# a header too short to hold the size reads as unknown, like any other file the
+# probe can't size
+def _probe(f: BinaryIO) -> Optional[ImageHeader]:
+    head = f.read(32)
+    if (
+        head.startswith(b"\x89PNG\r\n\x1a\n")
+        and head[12:16] == b"IHDR"
+        and len(head) >= 24
+    ):
+        width, height = struct.unpack(">II", head[16:24])
+        return ImageHeader("png", width, height)
+    if head[:6] in (b"GIF87a", b"GIF89a") and len(head) >= 10:
+        width, height = struct.unpack("<HH", head[6:10])
+        return ImageHeader("gif", width, height)
+    if head.startswith(b"BM") and len(head) >= 26:
+        width, height = struct.unpack("<ii", head[18:26])
+        return ImageHeader("bmp", width, abs(height))
+    if head[:4] == b"RIFF" and head[8:12] == b"WEBP":
+        return _probe_webp(head)
+    if head.startswith(b"\xff\xd8"):
+        f.seek(2)
+        return _probe_jpeg(f)
+    return None
+
+
+def _probe_webp(head: bytes) -> Optional[ImageHeader]:
+    if len(head) < 30:
+        return None
+    chunk = head[12:16]
+    if chunk == b"VP8 ":
+        width, height = struct.unpack("<HH", head[26:30])
+        return ImageHeader("webp", width & 0x3FFF, height & 0x3FFF)
+    if chunk == b"VP8L":
+        b0, b1, b2, b3 = head[21:25]
+        width = 1 + (((b1 & 0x3F) << 8) | b0)
+        height = 1 + (((b3 & 0x0F) << 10) | (b2 << 2) | ((b1 & 0xC0) >> 6))
+        return ImageHeader("webp", width, height)
+    if chunk == b"VP8X":
+        width = 1 + int.from_bytes(head[24:27], "little")
+        height = 1 + int.from_bytes(head[27:30], "little")
+        return ImageHeader("webp", width, height)
+    return None
+
+
+# recognizes formats the probe can't size too, and headers too short to size,
+# for error messages
+def sniff_format(data: bytes) -> Optional[str]:
+    header = probe_bytes(data)
+    if header is not None:
+        return header.format
+    for signature, format in _SIGNATURES:
+        if data.startswith(signature):
+            return format
+    if data[:4] == b"RIFF" and data[8:12] == b"WEBP":
+        return "webp"
+    if data[4:8] == b"ftyp":
+        # ISO base media files name their brand right after the ftyp box type
+        return _FTYP_BRANDS.get(data[8:12])
+    if data[:4] in (b"II*\x00", b"MM\x00*"):
+        return "tiff"
+    if data[:4] == b"\x00\x00\x01\x00":
+        return "ico"
+    return None
+
+
+# the formats probe sizes, so one it returns None for has a truncated header
+PROBED_FORMATS = frozenset({"png", "gif", "bmp", "webp", "jpeg"})
+
+_SIGNATURES = (
+    (b"\x89PNG\r\n\x1a\n", "png"),
+    (b"GIF87a", "gif"),
+    (b"GIF89a", "gif"),
+    (b"BM", "bmp"),
+    (b"\xff\xd8", "jpeg"),
+)
+
+_FTYP_BRANDS = {
+    b"heic": "heic",
+    b"heix": "heic",
+    b"mif1": "heif",
+    b"avif": "avif",
+}
+
+_TRAILERS = {
+    "png": b"IEND\xaeB`\x82",
+    "jpeg": b"\xff\xd9",
+    "gif": b";",
+}
+
+
+# only formats with a fixed trailer can be checked; the rest report False
+def looks_truncated(format: str, data: bytes) -> bool:
+    trailer = _TRAILERS.get(format)
+    # tolerate padding some encoders leave after the trailer
+    return trailer is not None and trailer not in data[-64:]
+
+
+# an APNG is a PNG with an acTL chunk ahead of its first IDAT
+def is_apng(data: bytes) -> bool:
+    if not data.startswith(_SIGNATURES[0][0]):
+        return False
+    pos = 8
+    while pos + 8 <= len(data):
+        (length,) = struct.unpack(">I", data[pos : pos + 4])
+        chunk = data[pos + 4 : pos + 8]
+        if chunk == b"acTL":
+            return True
+        if chunk == b"IDAT":
+            return False
+        pos += 12 + length
+    return False
+
+
+# SOF markers except DHT (0xC4), JPG (0xC8) and DAC (0xCC)
+_JPEG_SOF_MARKERS = frozenset(range(0xC0, 0xD0)) - {0xC4, 0xC8, 0xCC}
+
+
+def _probe_jpeg(f: BinaryIO) -> Optional[ImageHeader]:
+    orientation = 1
+    while True:
+        marker = f.read(2)
+        if len(marker) < 2 or marker[0] != 0xFF:
+            return None
+        if marker[1] == 0xFF:
+            f.seek(-1, 1)
+            continue
+        length_bytes = f.read(2)
+        if len(length_bytes) < 2:
+            return None
+        (length,) = struct.unpack(">H", length_bytes)
+        if marker[1] in _JPEG_SOF_MARKERS:
+            segment = f.read(5)
+            if len(segment) < 5:
+                return None
+            height, width = struct.unpack(">HH", segment[1:5])
+            if orientation >= 5:
+                # orientations 5 to 8 rotate the image by a quarter turn
+                width, height = height, width
+            return ImageHeader("jpeg", width, height, orientation)
+        if marker[1] == _JPEG_APP1:
+            orientation = _exif_orientation(f.read(length - 2)) or orientation
+        else:
+            f.seek(length - 2, 1)
+
+
+_JPEG_APP1 = 0xE1
+_EXIF_ORIENTATION_TAG = 0x0112
+
+
+# the Orientation tag of an APP1 segment's first IFD, or None if it has none
+def _exif_orientation(segment: bytes) -> Optional[int]:
+    if not segment.startswith(b"Exif\x00\x00"):
+        return None
+    tiff = segment[6:]
+    order = {b"II": "<", b"MM": ">"}.get(tiff[:2])
+    if order is None or len(tiff) < 8:
+        return None
+    (ifd,) = struct.unpack(order + "I", tiff[4:8])
+    if len(tiff) < ifd + 2:
+        return None
+    (count,) = struct.unpack(order + "H", tiff[ifd : ifd + 2])
+    for i in range(count):
+        entry = tiff[ifd + 2 + 12 * i : ifd + 14 + 12 * i]
+        if len(entry) < 12:
+            return None
+        tag, _, _, value = struct.unpack(order + "HHIH", entry[:10])
+        if tag == _EXIF_ORIENTATION_TAG:
+            return value if 1 <= value <= 8 else None
+    return None
@@ -1,5 +1,5 @@
 from dataclasses import replace
-from typing import Callable, cast
+from typing import Callable, Optional, cast
 
 from castella.core import (
     FillStyle,
@@ -10,14 +10,15 @@ from castella.core import (
     KeyCode,
     Kind,
     ObservableBase,
+    OutlineStyle,
     Painter,
     Point,
     Rect,
-    Size,
     Style,
     TextAlign,
     determine_font,
 )
+from castella.editing import caret_rect
 from castella.text import Text
 
 
@@ -163,26 +164,26 @@ class Input(Text):
         )
 
         if state.is_in_editing():
-            # fill_rect caret using get_caret_pos etc.
-            caret_pos_x = p.measure_text(str(state)[: state.get_caret_pos()])
-            p.style(Style(FillStyle(color="#AAAAAA")))
-            p.fill_rect(
-                Rect(
-                    Point(
-                        pos.x + caret_pos_x,
-                        pos.y - cap_height - (font_size - cap_height) / 2,
-                    ),
-                    Size(5, font_size),
-                )
+            caret = caret_rect(
+                p, str(state), state.get_caret_pos(), pos, font_size, width=5
             )
+            p.style(Style(FillStyle(color="#AAAAAA")))
+            p.fill_rect(caret)
 
     def focused(self) -> None:
         state = cast(InputState, self._state)
         state.start_editing()
+        # the parent draws the focus ring, and clears it again when unfocused
+        self.ask_parent_to_render(True)
 
     def unfocused(self) -> None:
         state = cast(InputState, self._state)
         state.finish_editing()
+        self.ask_parent_to_render(True)
+
+    def focus_outline(self) -> Optional[OutlineStyle]:
+        state = cast(InputState, self._state)
+        return OutlineStyle() if state.is_in_editing() else None
 
     def input_char(self, ev: InputCharEvent) -> None:
         state = cast(InputState, self._state)
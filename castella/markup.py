@@ -0,0 +1,139 @@
+from dataclasses import dataclass, replace
+from html.parser import HTMLParser
+from typing import Optional
+
+from castella.core import (
+    FontSlant,
+    FontWeight,
+    Painter,
+    Point,
+    Rect,
+    Size,
+    Style,
+)
+
+# a small HTML subset for formatted labels and chat messages: <b>, <i>, <u>,
+# <span color="..."> and <a href="...">. other tags are dropped but their text
+# is kept, so untrusted input can't break rendering.
+
+LINK_COLOR = "#1e88e5"
+
+
+@dataclass(slots=True, frozen=True)
+class Span:
+    text: str
+    bold: bool = False
+    italic: bool = False
+    underline: bool = False
+    color: Optional[str] = None
+    link: Optional[str] = None
+
+
+def parse(markup: str) -> list[Span]:
+    parser = _Parser()
+    parser.feed(markup)
+    parser.close()
+    return parser.spans
+
+
+# draws the spans as one line starting at pos, on the baseline, and returns the
+# width drawn
+def draw(p: Painter, spans: list[Span], pos: Point, style: Style) -> float:
+    x = pos.x
+    p.save()
+    try:
+        for span in spans:
+            p.style(_span_style(span, style))
+            p.fill_text(span.text, Point(x, pos.y), None)
+            width = p.measure_text(span.text)
+            if span.underline or span.link is not None:
+                p.fill_rect(Rect(Point(x, pos.y + 1), Size(width, 1)))
+            x += width
+    finally:
+        p.restore()
+    return x - pos.x
+
+
+def measure(p: Painter, spans: list[Span], style: Style) -> float:
+    width = 0.0
+    p.save()
+    try:
+        for span in spans:
+            p.style(_span_style(span, style))
+            width += p.measure_text(span.text)
+    finally:
+        p.restore()
+    return width
+
+
+# the link under x for spans drawn at pos, for handling clicks
+def link_at(
+    p: Painter, spans: list[Span], pos: Point, style: Style, x: float
+) -> Optional[str]:
+    left = pos.x
+    p.save()
+    try:
+        for span in spans:
+            p.style(_span_style(span, style))
+            right = left + p.measure_text(span.text)
+            if left <= x < right:
+                return span.link
+            left = right
+    finally:
+        p.restore()
+    return None
+
+
+def _span_style(span: Span, style: Style) -> Style:
+    font = replace(
+        style.font,
+        weight=FontWeight.BOLD if span.bold else FontWeight.NORMAL,
+        slant=FontSlant.ITALIC if span.italic else FontSlant.UPRIGHT,
+    )
+    color = span.color
+    if color is None and span.link is not None:
+        color = LINK_COLOR
+    fill = style.fill if color is None else replace(style.fill, color=color)
+    return replace(style, font=font, fill=fill)
+
+
+class _Parser(HTMLParser):
+    def __init__(self) -> None:
+        super().__init__(convert_charrefs=True)
+        self.spans: list[Span] = []
+        # the innermost open tag's span supplies the attributes for new text
+        self._stack: list[tuple[str, Span]] = [("", Span(""))]
+
+    def handle_starttag(
+        self, tag: str, attrs: list[tuple[str, Optional[str]]]
+    ) -> None:
+        current = self._stack[-1][1]
+        values = dict(attrs)
+        if tag == "b":
+            span = replace(current, bold=True)
+        elif tag == "i":
+            span = replace(current, italic=True)
+        elif tag == "u":
+            span = replace(current, underline=True)
+        elif tag == "span" and values.get("color"):
+            span = replace(current, color=values["color"])
+        elif tag == "a" and values.get("href"):
+            span = replace(current, link=values["href"])
+        else:
+            span = current
+        self._stack.append((tag, span))
+
+    def handle_endtag(self, tag: str) -> None:
+        # unclosed inner tags end with the outer one, as browsers do
+        for i in range(len(self._stack) - 1, 0, -1):
+            if self._stack[i][0] == tag:
+                del self._stack[i:]
+                return
+
+    def handle_data(self, data: str) -> None:
+        span = replace(self._stack[-1][1], text=data)
+        if self.spans and replace(self.spans[-1], text=data) == span:
+            # merge neighbours that only differ in text, e.g. around dropped tags
+            self.spans[-1] = replace(span, text=self.spans[-1].text + data)
+        else:
+            self.spans.append(span)
@@ -0,0 +1,291 @@
+from . import color
+from .color import Color
+
+# mirrors color.palette, e.g. palette["blue-grey"]["A100"] is BLUE_GREY_A100
+
+BLACK = Color.parse(color.BLACK)
+WHITE = Color.parse(color.WHITE)
+
+RED_50 = Color.parse(color.palette["red"]["50"])
+RED_100 = Color.parse(color.palette["red"]["100"])
+RED_200 = Color.parse(color.palette["red"]["200"])
+RED_300 = Color.parse(color.palette["red"]["300"])
+RED_400 = Color.parse(color.palette["red"]["400"])
+RED_500 = Color.parse(color.palette["red"]["500"])
+RED_600 = Color.parse(color.palette["red"]["600"])
+RED_700 = Color.parse(color.palette["red"]["700"])
+RED_800 = Color.parse(color.palette["red"]["800"])
+RED_900 = Color.parse(color.palette["red"]["900"])
+RED_A100 = Color.parse(color.palette["red"]["A100"])
+RED_A200 = Color.parse(color.palette["red"]["A200"])
+RED_A400 = Color.parse(color.palette["red"]["A400"])
+RED_A700 = Color.parse(color.palette["red"]["A700"])
+
+PINK_50 = Color.parse(color.palette["pink"]["50"])
+PINK_100 = Color.parse(color.palette["pink"]["100"])
+PINK_200 = Color.parse(color.palette["pink"]["200"])
+PINK_300 = Color.parse(color.palette["pink"]["300"])
+PINK_400 = Color.parse(color.palette["pink"]["400"])
+PINK_500 = Color.parse(color.palette["pink"]["500"])
+PINK_600 = Color.parse(color.palette["pink"]["600"])
+PINK_700 = Color.parse(color.palette["pink"]["700"])
+PINK_800 = Color.parse(color.palette["pink"]["800"])
+PINK_900 = Color.parse(color.palette["pink"]["900"])
+PINK_A100 = Color.parse(color.palette["pink"]["A100"])
+PINK_A200 = Color.parse(color.palette["pink"]["A200"])
+PINK_A400 = Color.parse(color.palette["pink"]["A400"])
+PINK_A700 = Color.parse(color.palette["pink"]["A700"])
+
+PURPLE_50 = Color.parse(color.palette["purple"]["50"])
+PURPLE_100 = Color.parse(color.palette["purple"]["100"])
+PURPLE_200 = Color.parse(color.palette["purple"]["200"])
+PURPLE_300 = Color.parse(color.palette["purple"]["300"])
+PURPLE_400 = Color.parse(color.palette["purple"]["400"])
+PURPLE_500 = Color.parse(color.palette["purple"]["500"])
+PURPLE_600 = Color.parse(color.palette["purple"]["600"])
+PURPLE_700 = Color.parse(color.palette["purple"]["700"])
+PURPLE_800 = Color.parse(color.palette["purple"]["800"])
+PURPLE_900 = Color.parse(color.palette["purple"]["900"])
+PURPLE_A100 = Color.parse(color.palette["purple"]["A100"])
+PURPLE_A200 = Color.parse(color.palette["purple"]["A200"])
+PURPLE_A400 = Color.parse(color.palette["purple"]["A400"])
+
+DEEP_PURPLE_50 = Color.parse(color.palette["deep-purple"]["50"])
+DEEP_PURPLE_100 = Color.parse(color.palette["deep-purple"]["100"])
+DEEP_PURPLE_200 = Color.parse(color.palette["deep-purple"]["200"])
+DEEP_PURPLE_300 = Color.parse(color.palette["deep-purple"]["300"])
+DEEP_PURPLE_400 = Color.parse(color.palette["deep-purple"]["400"])
+DEEP_PURPLE_500 = Color.parse(color.palette["deep-purple"]["500"])
+DEEP_PURPLE_600 = Color.parse(color.palette["deep-purple"]["600"])
+DEEP_PURPLE_700 = Color.parse(color.palette["deep-purple"]["700"])
+DEEP_PURPLE_800 = Color.parse(color.palette["deep-purple"]["800"])
+DEEP_PURPLE_900 = Color.parse(color.palette["deep-purple"]["900"])
+DEEP_PURPLE_A100 = Color.parse(color.palette["deep-purple"]["A100"])
+DEEP_PURPLE_A200 = Color.parse(color.palette["deep-purple"]["A200"])
+DEEP_PURPLE_A400 = Color.parse(color.palette["deep-purple"]["A400"])
+DEEP_PURPLE_A700 = Color.parse(color.palette["deep-purple"]["A700"])
+
+INDIGO_50 = Color.parse(color.palette["indigo"]["50"])
+INDIGO_100 = Color.parse(color.palette["indigo"]["100"])
+INDIGO_200 = Color.parse(color.palette["indigo"]["200"])
+INDIGO_300 = Color.parse(color.palette["indigo"]["300"])
+INDIGO_400 = Color.parse(color.palette["indigo"]["400"])
+INDIGO_500 = Color.parse(color.palette["indigo"]["500"])
+INDIGO_600 = Color.parse(color.palette["indigo"]["600"])
+INDIGO_700 = Color.parse(color.palette["indigo"]["700"])
+INDIGO_800 = Color.parse(color.palette["indigo"]["800"])
+INDIGO_900 = Color.parse(color.palette["indigo"]["900"])
+INDIGO_A100 = Color.parse(color.palette["indigo"]["A100"])
+INDIGO_A200 = Color.parse(color.palette["indigo"]["A200"])
+INDIGO_A400 = Color.parse(color.palette["indigo"]["A400"])
+INDIGO_A700 = Color.parse(color.palette["indigo"]["A700"])
+
+BLUE_50 = Color.parse(color.palette["blue"]["50"])
+BLUE_100 = Color.parse(color.palette["blue"]["100"])
+BLUE_200 = Color.parse(color.palette["blue"]["200"])
+BLUE_300 = Color.parse(color.palette["blue"]["300"])
+BLUE_400 = Color.parse(color.palette["blue"]["400"])
+BLUE_500 = Color.parse(color.palette["blue"]["500"])
+BLUE_600 = Color.parse(color.palette["blue"]["600"])
+BLUE_700 = Color.parse(color.palette["blue"]["700"])
+BLUE_800 = Color.parse(color.palette["blue"]["800"])
+BLUE_900 = Color.parse(color.palette["blue"]["900"])
+BLUE_A100 = Color.parse(color.palette["blue"]["A100"])
+BLUE_A200 = Color.parse(color.palette["blue"]["A200"])
+BLUE_A400 = Color.parse(color.palette["blue"]["A400"])
+BLUE_A700 = Color.parse(color.palette["blue"]["A700"])
+
+LIGHT_BLUE_50 = Color.parse(color.palette["light-blue"]["50"])
+LIGHT_BLUE_100 = Color.parse(color.palette["light-blue"]["100"])
+LIGHT_BLUE_200 = Color.parse(color.palette["light-blue"]["200"])
+LIGHT_BLUE_300 = Color.parse(color.palette["light-blue"]["300"])
+LIGHT_BLUE_400 = Color.parse(color.palette["light-blue"]["400"])
+LIGHT_BLUE_500 = Color.parse(color.palette["light-blue"]["500"])
+LIGHT_BLUE_600 = Color.parse(color.palette["light-blue"]["600"])
+LIGHT_BLUE_700 = Color.parse(color.palette["light-blue"]["700"])
+LIGHT_BLUE_800 = Color.parse(color.palette["light-blue"]["800"])
+LIGHT_BLUE_900 = Color.parse(color.palette["light-blue"]["900"])
+LIGHT_BLUE_A100 = Color.parse(color.palette["light-blue"]["A100"])
+LIGHT_BLUE_A200 = Color.parse(color.palette["light-blue"]["A200"])
+LIGHT_BLUE_A400 = Color.parse(color.palette["light-blue"]["A400"])
+LIGHT_BLUE_A700 = Color.parse(color.palette["light-blue"]["A700"])
+
+CYAN_50 = Color.parse(color.palette["cyan"]["50"])
+CYAN_100 = Color.parse(color.palette["cyan"]["100"])
+CYAN_200 = Color.parse(color.palette["cyan"]["200"])
+CYAN_300 = Color.parse(color.palette["cyan"]["300"])
+CYAN_400 = Color.parse(color.palette["cyan"]["400"])
+CYAN_500 = Color.parse(color.palette["cyan"]["500"])
+CYAN_600 = Color.parse(color.palette["cyan"]["600"])
+CYAN_700 = Color.parse(color.palette["cyan"]["700"])
+CYAN_800 = Color.parse(color.palette["cyan"]["800"])
+CYAN_900 = Color.parse(color.palette["cyan"]["900"])
+CYAN_A100 = Color.parse(color.palette["cyan"]["A100"])
+CYAN_A200 = Color.parse(color.palette["cyan"]["A200"])
+CYAN_A400 = Color.parse(color.palette["cyan"]["A400"])
+CYAN_A700 = Color.parse(color.palette["cyan"]["A700"])
+
+TEAL_50 = Color.parse(color.palette["teal"]["50"])
+TEAL_100 = Color.parse(color.palette["teal"]["100"])
+TEAL_200 = Color.parse(color.palette["teal"]["200"])
+TEAL_300 = Color.parse(color.palette["teal"]["300"])
+TEAL_400 = Color.parse(color.palette["teal"]["400"])
+TEAL_500 = Color.parse(color.palette["teal"]["500"])
+TEAL_600 = Color.parse(color.palette["teal"]["600"])
+TEAL_700 = Color.parse(color.palette["teal"]["700"])
+TEAL_800 = Color.parse(color.palette["teal"]["800"])
+TEAL_900 = Color.parse(color.palette["teal"]["900"])
+TEAL_A100 = Color.parse(color.palette["teal"]["A100"])
+TEAL_A200 = Color.parse(color.palette["teal"]["A200"])
+TEAL_A400 = Color.parse(color.palette["teal"]["A400"])
+TEAL_A700 = Color.parse(color.palette["teal"]["A700"])
+
+GREEN_50 = Color.parse(color.palette["green"]["50"])
+GREEN_100 = Color.parse(color.palette["green"]["100"])
+GREEN_200 = Color.parse(color.palette["green"]["200"])
+GREEN_300 = Color.parse(color.palette["green"]["300"])
+GREEN_400 = Color.parse(color.palette["green"]["400"])
+GREEN_500 = Color.parse(color.palette["green"]["500"])
+GREEN_600 = Color.parse(color.palette["green"]["600"])
+GREEN_700 = Color.parse(color.palette["green"]["700"])
+GREEN_800 = Color.parse(color.palette["green"]["800"])
+GREEN_900 = Color.parse(color.palette["green"]["900"])
+GREEN_A100 = Color.parse(color.palette["green"]["A100"])
+GREEN_A200 = Color.parse(color.palette["green"]["A200"])
+GREEN_A400 = Color.parse(color.palette["green"]["A400"])
+GREEN_A700 = Color.parse(color.palette["green"]["A700"])
+
+LIGHT_GREEN_50 = Color.parse(color.palette["light-green"]["50"])
+LIGHT_GREEN_100 = Color.parse(color.palette["light-green"]["100"])
+LIGHT_GREEN_200 = Color.parse(color.palette["light-green"]["200"])
+LIGHT_GREEN_300 = Color.parse(color.palette["light-green"]["300"])
+LIGHT_GREEN_400 = Color.parse(color.palette["light-green"]["400"])
+LIGHT_GREEN_500 = Color.parse(color.palette["light-green"]["500"])
+LIGHT_GREEN_600 = Color.parse(color.palette["light-green"]["600"])
+LIGHT_GREEN_700 = Color.parse(color.palette["light-green"]["700"])
+LIGHT_GREEN_800 = Color.parse(color.palette["light-green"]["800"])
+LIGHT_GREEN_900 = Color.parse(color.palette["light-green"]["900"])
+LIGHT_GREEN_A100 = Color.parse(color.palette["light-green"]["A100"])
+LIGHT_GREEN_A200 = Color.parse(color.palette["light-green"]["A200"])
+LIGHT_GREEN_A400 = Color.parse(color.palette["light-green"]["A400"])
+LIGHT_GREEN_A700 = Color.parse(color.palette["light-green"]["A700"])
+
+LIME_50 = Color.parse(color.palette["lime"]["50"])
+LIME_100 = Color.parse(color.palette["lime"]["100"])
+LIME_200 = Color.parse(color.palette["lime"]["200"])
+LIME_300 = Color.parse(color.palette["lime"]["300"])
+LIME_400 = Color.parse(color.palette["lime"]["400"])
+LIME_500 = Color.parse(color.palette["lime"]["500"])
+LIME_600 = Color.parse(color.palette["lime"]["600"])
+LIME_700 = Color.parse(color.palette["lime"]["700"])
+LIME_800 = Color.parse(color.palette["lime"]["800"])
+LIME_900 = Color.parse(color.palette["lime"]["900"])
+LIME_A100 = Color.parse(color.palette["lime"]["A100"])
+LIME_A200 = Color.parse(color.palette["lime"]["A200"])
+LIME_A400 = Color.parse(color.palette["lime"]["A400"])
+LIME_A700 = Color.parse(color.palette["lime"]["A700"])
+
+YELLOW_50 = Color.parse(color.palette["yellow"]["50"])
+YELLOW_100 = Color.parse(color.palette["yellow"]["100"])
+YELLOW_200 = Color.parse(color.palette["yellow"]["200"])
+YELLOW_300 = Color.parse(color.palette["yellow"]["300"])
+YELLOW_400 = Color.parse(color.palette["yellow"]["400"])
+YELLOW_500 = Color.parse(color.palette["yellow"]["500"])
+YELLOW_600 = Color.parse(color.palette["yellow"]["600"])
+YELLOW_700 = Color.parse(color.palette["yellow"]["700"])
+YELLOW_800 = Color.parse(color.palette["yellow"]["800"])
+YELLOW_900 = Color.parse(color.palette["yellow"]["900"])
+YELLOW_A100 = Color.parse(color.palette["yellow"]["A100"])
+YELLOW_A200 = Color.parse(color.palette["yellow"]["A200"])
+YELLOW_A400 = Color.parse(color.palette["yellow"]["A400"])
+YELLOW_A700 = Color.parse(color.palette["yellow"]["A700"])
+
+AMBER_50 = Color.parse(color.palette["amber"]["50"])
+AMBER_100 = Color.parse(color.palette["amber"]["100"])
+AMBER_200 = Color.parse(color.palette["amber"]["200"])
+AMBER_300 = Color.parse(color.palette["amber"]["300"])
+AMBER_400 = Color.parse(color.palette["amber"]["400"])
+AMBER_500 = Color.parse(color.palette["amber"]["500"])
+AMBER_600 = Color.parse(color.palette["amber"]["600"])
+AMBER_700 = Color.parse(color.palette["amber"]["700"])
+AMBER_800 = Color.parse(color.palette["amber"]["800"])
+AMBER_900 = Color.parse(color.palette["amber"]["900"])
+AMBER_A100 = Color.parse(color.palette["amber"]["A100"])
+AMBER_A200 = Color.parse(color.palette["amber"]["A200"])
+AMBER_A400 = Color.parse(color.palette["amber"]["A400"])
+AMBER_A700 = Color.parse(color.palette["amber"]["A700"])
+
+ORANGE_50 = Color.parse(color.palette["orange"]["50"])
+ORANGE_100 = Color.parse(color.palette["orange"]["100"])
+ORANGE_200 = Color.parse(color.palette["orange"]["200"])
+ORANGE_300 = Color.parse(color.palette["orange"]["300"])
+ORANGE_400 = Color.parse(color.palette["orange"]["400"])
+ORANGE_500 = Color.parse(color.palette["orange"]["500"])
+ORANGE_600 = Color.parse(color.palette["orange"]["600"])
+ORANGE_700 = Color.parse(color.palette["orange"]["700"])
+ORANGE_800 = Color.parse(color.palette["orange"]["800"])
+ORANGE_900 = Color.parse(color.palette["orange"]["900"])
+ORANGE_A100 = Color.parse(color.palette["orange"]["A100"])
+ORANGE_A200 = Color.parse(color.palette["orange"]["A200"])
+ORANGE_A400 = Color.parse(color.palette["orange"]["A400"])
+ORANGE_A700 = Color.parse(color.palette["orange"]["A700"])
+
+DEEP_ORANGE_50 = Color.parse(color.palette["deep-orange"]["50"])
+DEEP_ORANGE_100 = Color.parse(color.palette["deep-orange"]["100"])
+DEEP_ORANGE_200 = Color.parse(color.palette["deep-orange"]["200"])
+DEEP_ORANGE_300 = Color.parse(color.palette["deep-orange"]["300"])
+DEEP_ORANGE_400 = Color.parse(color.palette["deep-orange"]["400"])
+DEEP_ORANGE_500 = Color.parse(color.palette["deep-orange"]["500"])
+DEEP_ORANGE_600 = Color.parse(color.palette["deep-orange"]["600"])
+DEEP_ORANGE_700 = Color.parse(color.palette["deep-orange"]["700"])
+DEEP_ORANGE_800 = Color.parse(color.palette["deep-orange"]["800"])
+DEEP_ORANGE_900 = Color.parse(color.palette["deep-orange"]["900"])
+DEEP_ORANGE_A100 = Color.parse(color.palette["deep-orange"]["A100"])
+DEEP_ORANGE_A200 = Color.parse(color.palette["deep-orange"]["A200"])
+DEEP_ORANGE_A400 = Color.parse(color.palette["deep-orange"]["A400"])
+DEEP_ORANGE_A700 = Color.parse(color.palette["deep-orange"]["A700"])
+
+BROWN_50 = Color.parse(color.palette["brown"]["50"])
+BROWN_100 = Color.parse(color.palette["brown"]["100"])
+BROWN_200 = Color.parse(color.palette["brown"]["200"])
+BROWN_300 = Color.parse(color.palette["brown"]["300"])
+BROWN_400 = Color.parse(color.palette["brown"]["400"])
+BROWN_500 = Color.parse(color.palette["brown"]["500"])
+BROWN_600 = Color.parse(color.palette["brown"]["600"])
+BROWN_700 = Color.parse(color.palette["brown"]["700"])
+BROWN_800 = Color.parse(color.palette["brown"]["800"])
+BROWN_900 = Color.parse(color.palette["brown"]["900"])
+BROWN_A100 = Color.parse(color.palette["brown"]["A100"])
+BROWN_A200 = Color.parse(color.palette["brown"]["A200"])
+BROWN_A400 = Color.parse(color.palette["brown"]["A400"])
+BROWN_A700 = Color.parse(color.palette["brown"]["A700"])
+
+GREY_50 = Color.parse(color.palette["grey"]["50"])
+GREY_100 = Color.parse(color.palette["grey"]["100"])
+GREY_200 = Color.parse(color.palette["grey"]["200"])
+GREY_300 = Color.parse(color.palette["grey"]["300"])
+GREY_400 = Color.parse(color.palette["grey"]["400"])
+GREY_500 = Color.parse(color.palette["grey"]["500"])
+GREY_600 = Color.parse(color.palette["grey"]["600"])
+GREY_700 = Color.parse(color.palette["grey"]["700"])
+GREY_800 = Color.parse(color.palette["grey"]["800"])
+GREY_900 = Color.parse(color.palette["grey"]["900"])
+GREY_A100 = Color.parse(color.palette["grey"]["A100"])
+GREY_A200 = Color.parse(color.palette["grey"]["A200"])
+GREY_A400 = Color.parse(color.palette["grey"]["A400"])
+GREY_A700 = Color.parse(color.palette["grey"]["A700"])
+
+BLUE_GREY_50 = Color.parse(color.palette["blue-grey"]["50"])
+BLUE_GREY_100 = Color.parse(color.palette["blue-grey"]["100"])
+BLUE_GREY_200 = Color.parse(color.palette["blue-grey"]["200"])
+BLUE_GREY_300 = Color.parse(color.palette["blue-grey"]["300"])
+BLUE_GREY_400 = Color.parse(color.palette["blue-grey"]["400"])
+BLUE_GREY_500 = Color.parse(color.palette["blue-grey"]["500"])
+BLUE_GREY_600 = Color.parse(color.palette["blue-grey"]["600"])
+BLUE_GREY_700 = Color.parse(color.palette["blue-grey"]["700"])
+BLUE_GREY_800 = Color.parse(color.palette["blue-grey"]["800"])
+BLUE_GREY_900 = Color.parse(color.palette["blue-grey"]["900"])
+BLUE_GREY_A100 = Color.parse(color.palette["blue-grey"]["A100"])
+BLUE_GREY_A200 = Color.parse(color.palette["blue-grey"]["A200"])
+BLUE_GREY_A400 = Color.parse(color.palette["blue-grey"]["A400"])
+BLUE_GREY_A700 = Color.parse(color.palette["blue-grey"]["A700"])
@@ -206,15 +206,13 @@ class Row(Layout):
 
     def contain_in_content_area(self, p: Point) -> bool:
         if self._scrollable and self.content_width() > self.get_width():
-            return (self._pos.x < p.x < self._pos.x + self._size.width) and (
-                self._pos.y < p.y < self._pos.y + self._size.height - SCROLL_BAR_SIZE
-            )
+            return Rect(
+                self._pos, self._size - Size(0, SCROLL_BAR_SIZE)
+            ).contain_strictly(p)
         return self.contain_in_my_area(p)
 
     def contain_in_my_area(self, p: Point) -> bool:
-        return (self._pos.x < p.x < self._pos.x + self._size.width) and (
-            self._pos.y < p.y < self._pos.y + self._size.height
-        )
+        return Rect(self._pos, self._size).contain_strictly(p)
 
     def _relocate_children(self, p: Painter) -> None:
         self._resize_children(p)
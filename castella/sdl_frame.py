@@ -1,3 +1,4 @@
+import ctypes
 import platform
 import threading
 from ctypes import byref, c_int
@@ -12,8 +13,6 @@ from . import core
 from . import skia_painter as painter
 
 if platform.system() == "Windows":
-    import ctypes
-
     user32 = ctypes.windll.user32
     user32.SetProcessDPIAware()
 
@@ -52,19 +51,46 @@ class Frame:
 
         self._window = window
         self._size = core.Size(width, height)
+        self._sdl_surface = None
         self._update_surface_and_painter()
         self._update_event_queue = SimpleQueue()
 
     def _update_surface_and_painter(self) -> None:
         zengl.context(zengl.loader(headless=True))
 
-        info = skia.ImageInfo.MakeN32Premul(self._size.width, self._size.height)
+        # without a color space skia skips converting images with embedded ICC profiles
+        info = skia.ImageInfo.MakeN32Premul(
+            self._size.width, self._size.height, skia.ColorSpace.MakeSRGB()
+        )
         surface = skia.Surface.MakeRenderTarget(
             skia.GrDirectContext.MakeGL(), skia.Budgeted.kNo, info
         )
 
         self._surface = surface
-        self._painter = painter.Painter(self, self._surface)
+        self._painter = core.instrument(painter.Painter(self, self._surface))
+        self._update_pixels(info)
+
+    # every flush reads the frame back into this one buffer, which the SDL surface
+    # wraps, instead of allocating a new copy per frame
+    def _update_pixels(self, info: skia.ImageInfo) -> None:
+        if self._sdl_surface is not None:
+            sdl.SDL_FreeSurface(self._sdl_surface)
+
+        width = info.width()
+        height = info.height()
+        self._pixels_info = info
+        self._pixels = bytearray(self.PIXEL_PITCH_FACTOR * width * height)
+        self._pixels_view = (ctypes.c_char * len(self._pixels)).from_buffer(
+            self._pixels
+        )
+        self._sdl_surface = sdl.SDL_CreateRGBSurfaceFrom(
+            self._pixels_view,
+            width,
+            height,
+            self.PIXEL_DEPTH,
+            self.PIXEL_PITCH_FACTOR * width,
+            *self._rgba_masks
+        )
 
     def on_mouse_down(self, handler: Callable[[core.MouseEvent], None]) -> None:
         self._callback_on_mouse_down = handler
@@ -90,6 +116,7 @@ class Frame:
     def _on_redraw(self, w, h, handler: Callable[[core.Painter, bool], None]) -> None:
         self._size = core.Size(w, h)
         self._update_surface_and_painter()
+        self._painter.begin_frame()
         handler(self._painter, True)
 
     def get_painter(self) -> core.Painter:
@@ -119,35 +146,25 @@ class Frame:
             pos = w.get_pos()
             clippedRect = core.Rect(core.Point(0, 0), w.get_size())
 
-        self._painter.save()
-        try:
-            self._painter.translate(pos)
-            if clippedRect is not None:
-                self._painter.clip(clippedRect)
-            ev.target.redraw(self._painter, ev.completely)
+        self._painter.begin_frame()
+        with self._painter.translated(pos):
+            depth = self._painter.save_count()
+            try:
+                if clippedRect is not None:
+                    self._painter.clip(clippedRect)
+                ev.target.redraw(self._painter, ev.completely)
+            finally:
+                self._painter.restore_to_count(depth)
             self._painter.flush()
-        finally:
-            self._painter.restore()
 
     def flush(self) -> None:
-        skia_image = self._surface.makeImageSnapshot()
-        skia_bytes = skia_image.tobytes()
-
-        size = self._size
-        width = size.width
-        height = size.height
-        sdl_surface = sdl.SDL_CreateRGBSurfaceFrom(
-            skia_bytes,
-            width,
-            height,
-            self.PIXEL_DEPTH,
-            self.PIXEL_PITCH_FACTOR * width,
-            *self._rgba_masks
-        )
+        width = self._pixels_info.width()
+        height = self._pixels_info.height()
+        self._painter.read_pixels_into(self._pixels)
 
         rect = sdl.SDL_Rect(0, 0, width, height)
         window_surface = sdl.SDL_GetWindowSurface(self._window)
-        sdl.SDL_BlitSurface(sdl_surface, rect, window_surface, rect)
+        sdl.SDL_BlitSurface(self._sdl_surface, rect, window_surface, rect)
         sdl.SDL_UpdateWindowSurface(self._window)
 
     def clear(self) -> None:
@@ -1,23 +1,34 @@
+import difflib
+import hashlib
 import io
+import json
+import logging
+import os
+import string
+import tempfile
+import threading
+import time
+import urllib.error
 import urllib.request
-from functools import cache
-from typing import Optional, cast
+from collections import OrderedDict
+from collections.abc import Iterable, Sequence
+from concurrent.futures import ThreadPoolExecutor
+from dataclasses import dataclass
+from functools import lru_cache
+from typing import BinaryIO, Callable, Optional, cast
 
 import numpy as np
 import skia
 
-from . import core
+from . import core, image_header
+from .color import parse as parse_color
 
+logger = logging.getLogger(__name__)
 
-def _code2rgb(color_code):
-    r = int(color_code[1:3], 16)
-    g = int(color_code[3:5], 16)
-    b = int(color_code[5:7], 16)
-    return (r, g, b)
 
-
-def _to_skia_color(color: str) -> int:
-    return skia.ColorSetRGB(*_code2rgb(color))
+def _to_skia_color(color: str | core.Color) -> int:
+    r, g, b, a = parse_color(color)
+    return skia.ColorSetARGB(a, r, g, b)
 
 
 def _to_skia_rect(rect: core.Rect) -> skia.Rect:
@@ -26,12 +37,237 @@ def _to_skia_rect(rect: core.Rect) -> skia.Rect:
     )
 
 
-@cache
+def _to_skia_image_format(format: core.ImageFormat) -> skia.EncodedImageFormat:
+    if format is core.ImageFormat.PNG:
+        return skia.EncodedImageFormat.kPNG
+    elif format is core.ImageFormat.JPEG:
+        return skia.EncodedImageFormat.kJPEG
+    else:
+        return skia.EncodedImageFormat.kWEBP
+
+
+def _to_skia_tile_mode(tile_mode: core.TileMode) -> skia.TileMode:
+    if tile_mode is core.TileMode.CLAMP:
+        return skia.TileMode.kClamp
+    elif tile_mode is core.TileMode.REPEAT:
+        return skia.TileMode.kRepeat
+    elif tile_mode is core.TileMode.MIRROR:
+        return skia.TileMode.kMirror
+    else:
+        return skia.TileMode.kDecal
+
+
+def _to_skia_shader(gradient: core.Gradient) -> skia.Shader:
+    colors = [_to_skia_color(stop.color) for stop in gradient.stops]
+    positions = [stop.offset for stop in gradient.stops]
+    mode = _to_skia_tile_mode(gradient.tile_mode)
+    if isinstance(gradient, core.LinearGradient):
+        return skia.GradientShader.MakeLinear(
+            [
+                skia.Point(gradient.start.x, gradient.start.y),
+                skia.Point(gradient.end.x, gradient.end.y),
+            ],
+            colors,
+            positions,
+            mode,
+        )
+    elif isinstance(gradient, core.RadialGradient):
+        return skia.GradientShader.MakeRadial(
+            skia.Point(gradient.center.x, gradient.center.y),
+            gradient.radius,
+            colors,
+            positions,
+            mode,
+        )
+    else:
+        return skia.GradientShader.MakeSweep(
+            gradient.center.x,
+            gradient.center.y,
+            colors,
+            positions,
+            mode,
+            gradient.start_angle,
+            gradient.end_angle,
+        )
+
+
+def _to_skia_image_filter(f: Optional[core.ImageFilter]) -> Optional[skia.ImageFilter]:
+    if f is None:
+        return None
+    elif isinstance(f, core.BlurFilter):
+        return skia.ImageFilters.Blur(
+            f.sigma_x, f.sigma_y, skia.TileMode.kDecal, _to_skia_image_filter(f.input)
+        )
+    elif isinstance(f, core.DropShadowFilter):
+        return skia.ImageFilters.DropShadow(
+            f.dx,
+            f.dy,
+            f.sigma,
+            f.sigma,
+            _to_skia_color(f.color),
+            _to_skia_image_filter(f.input),
+        )
+    elif isinstance(f, core.ColorMatrixFilter):
+        return skia.ImageFilters.ColorFilter(
+            skia.ColorFilters.Matrix(list(f.matrix)), _to_skia_image_filter(f.input)
+        )
+    elif isinstance(f, core.DisplacementFilter):
+        return skia.ImageFilters.DisplacementMap(
+            _SKIA_COLOR_CHANNELS[f.x_channel],
+            _SKIA_COLOR_CHANNELS[f.y_channel],
+            f.scale,
+            _to_skia_image_filter(f.displacement),
+            _to_skia_image_filter(f.input),
+        )
+    else:
+        return skia.ImageFilters.Blend(
+            _SKIA_BLEND_MODES[f.mode],
+            _to_skia_image_filter(f.background),
+            _to_skia_image_filter(f.foreground),
+        )
+
+
+_SKIA_COLOR_CHANNELS = {
+    core.ColorChannel.R: skia.ColorChannel.kR,
+    core.ColorChannel.G: skia.ColorChannel.kG,
+    core.ColorChannel.B: skia.ColorChannel.kB,
+    core.ColorChannel.A: skia.ColorChannel.kA,
+}
+
+_SKIA_BLEND_MODES = {
+    core.BlendMode.SRC_OVER: skia.BlendMode.kSrcOver,
+    core.BlendMode.MULTIPLY: skia.BlendMode.kMultiply,
+    core.BlendMode.SCREEN: skia.BlendMode.kScreen,
+    core.BlendMode.OVERLAY: skia.BlendMode.kOverlay,
+    core.BlendMode.DARKEN: skia.BlendMode.kDarken,
+    core.BlendMode.LIGHTEN: skia.BlendMode.kLighten,
+}
+
+
+# a path rather than per-segment lines, so segments join instead of overlapping
+def _to_skia_poly(points: Sequence[core.Point], close: bool) -> skia.Path:
+    path = skia.Path()
+    path.addPoly([skia.Point(p.x, p.y) for p in points], close)
+    return path
+
+
+def _to_skia_path(path: core.Path) -> skia.Path:
+    sp = skia.Path()
+    for segment in path.segments:
+        if isinstance(segment, core.MoveTo):
+            sp.moveTo(segment.to.x, segment.to.y)
+        elif isinstance(segment, core.LineTo):
+            sp.lineTo(segment.to.x, segment.to.y)
+        elif isinstance(segment, core.QuadTo):
+            c = segment.control
+            sp.quadTo(c.x, c.y, segment.to.x, segment.to.y)
+        elif isinstance(segment, core.CubicTo):
+            c1, c2 = segment.control1, segment.control2
+            sp.cubicTo(c1.x, c1.y, c2.x, c2.y, segment.to.x, segment.to.y)
+        elif isinstance(segment, core.ArcTo):
+            c = segment.corner
+            sp.arcTo(c.x, c.y, segment.to.x, segment.to.y, segment.radius)
+        else:
+            sp.close()
+    return sp
+
+
+def _make_stroke_paint(style: core.Style) -> skia.Paint:
+    return skia.Paint(
+        Color=_to_skia_color(style.stroke.color),
+        Style=skia.Paint.kStroke_Style,
+        StrokeWidth=style.line.width,
+        StrokeCap=_to_skia_stroke_cap(style.line.cap),
+        AntiAlias=True,
+    )
+
+
+def _to_skia_stroke_cap(cap: core.LineCap) -> skia.Paint.Cap:
+    if cap is core.LineCap.BUTT:
+        return skia.Paint.kButt_Cap
+    elif cap is core.LineCap.ROUND:
+        return skia.Paint.kRound_Cap
+    else:
+        return skia.Paint.kSquare_Cap
+
+
+def _make_fill_paint(fill: core.FillStyle, anti_alias: bool = False) -> skia.Paint:
+    paint = skia.Paint(Style=skia.Paint.kFill_Style, AntiAlias=anti_alias)
+    if fill.gradient is not None:
+        paint.setShader(_to_skia_shader(fill.gradient))
+        paint.setDither(fill.dither)
+    else:
+        paint.setColor(_to_skia_color(fill.color))
+    return paint
+
+
+@dataclass(slots=True, frozen=True)
+class BuildInfo:
+    skia_version: str  # skia-python's version, whose major part is the Skia milestone
+    backends: tuple[str, ...]
+    features: tuple[str, ...]
+
+
+_BACKEND_FACTORIES = (
+    ("gl", "MakeGL"),
+    ("metal", "MakeMetal"),
+    ("vulkan", "MakeVulkan"),
+)
+_FEATURE_MODULES = (("svg", "SVGDOM"), ("pdf", "PDF"), ("textlayout", "textlayout"))
+
+
+def build_info() -> BuildInfo:
+    # probed rather than hard-coded, since skia-python wheels differ by platform
+    backends = tuple(
+        name
+        for name, factory in _BACKEND_FACTORIES
+        if hasattr(skia.GrDirectContext, factory)
+    )
+    features = tuple(name for name, attr in _FEATURE_MODULES if hasattr(skia, attr))
+    return BuildInfo(getattr(skia, "__version__", "unknown"), backends, features)
+
+
+DEFAULT_FONT_CACHE_LIMIT = 256
+
+
+@dataclass(slots=True, frozen=True)
+class FontCacheStats:
+    hits: int
+    misses: int
+    size: int
+    limit: int
+
+
+_strict_font_families = False
+
+
+# by default an unknown family silently falls back to the system default;
+# strict mode raises instead, to catch fonts missing from a package
+def set_strict_font_families(strict: bool) -> None:
+    global _strict_font_families
+    _strict_font_families = strict
+    clear_font_cache()
+
+
+@lru_cache(maxsize=DEFAULT_FONT_CACHE_LIMIT)
 def _get_font_face(font_family: str, font_style: skia.FontStyle) -> skia.Typeface:
+    # "" asks for the system default, so it's never missing
+    if _strict_font_families and font_family != "":
+        _check_font_family(font_family)
     return skia.Typeface(font_family, font_style)
 
 
-@cache
+def _check_font_family(font_family: str) -> None:
+    font_mgr = skia.FontMgr()
+    if font_mgr.matchFamily(font_family).count() > 0:
+        return
+    families = [font_mgr.getFamilyName(i) for i in range(font_mgr.countFamilies())]
+    nearest = difflib.get_close_matches(font_family, families)
+    suggestion = f"; did you mean {', '.join(nearest)}?" if nearest else ""
+    raise ValueError(f"Font family {font_family!r} is not installed{suggestion}")
+
+
+@lru_cache(maxsize=DEFAULT_FONT_CACHE_LIMIT)
 def _to_skia_font(font: core.Font) -> skia.Font:
     if font.weight is core.FontWeight.NORMAL:
         font_weight = skia.FontStyle.kNormal_Weight
@@ -47,23 +283,110 @@ def _to_skia_font(font: core.Font) -> skia.Font:
     return skia.Font(_get_font_face(font.family, font_style), font.size)
 
 
-class Painter:
+# counts skia.Font lookups, which is what every text call goes through
+def get_font_cache_stats() -> FontCacheStats:
+    info = _to_skia_font.cache_info()
+    return FontCacheStats(info.hits, info.misses, info.currsize, info.maxsize)
+
+
+def clear_font_cache() -> None:
+    _to_skia_font.cache_clear()
+    _get_font_face.cache_clear()
+    _find_fallback_family.cache_clear()
+
+
+@dataclass(slots=True, frozen=True)
+class GlyphCoverage:
+    char: str
+    covered: bool  # whether the requested family has a glyph for char
+    fallback: Optional[str]  # an installed family that has one, if any
+
+
+# text is drawn with the requested typeface only, so an uncovered character
+# renders as tofu; fallback tells which font would fix it. checks each distinct
+# code point rather than grapheme clusters.
+def font_coverage(text: str, family: str) -> list[GlyphCoverage]:
+    font_style = skia.FontStyle()
+    typeface = _get_font_face(family, font_style)
+    coverage = []
+    for char in dict.fromkeys(text):
+        if typeface.unicharToGlyph(ord(char)) != 0:
+            coverage.append(GlyphCoverage(char, True, None))
+            continue
+        fallback = _find_fallback_family(family, ord(char))
+        coverage.append(GlyphCoverage(char, False, fallback))
+    return coverage
+
+
+# the font manager walks every installed font (fontconfig on Linux) per lookup
+@lru_cache(maxsize=1024)
+def _find_fallback_family(family: str, codepoint: int) -> Optional[str]:
+    font_mgr = skia.FontMgr()
+    match = font_mgr.matchFamilyStyleCharacter(family, skia.FontStyle(), [], codepoint)
+    return None if match is None else match.getFamilyName()
+
+
+# minimal containers often ship no fonts at all, and then all text is invisible
+def has_usable_font() -> bool:
+    return skia.FontMgr().countFamilies() > 0
+
+
+# loads the typefaces and fills skia's glyph cache ahead of the first frame
+def warm_up_fonts(fonts: Iterable[core.Font], text: str = string.printable) -> None:
+    for font in fonts:
+        skia_font = _to_skia_font(font)
+        glyphs = skia_font.textToGlyphs(text)
+        skia_font.getWidths(glyphs)
+        skia_font.getPaths(glyphs)
+
+
+# seconds spent in each phase of the last flushed frame
+@dataclass(slots=True, frozen=True)
+class FrameTimings:
+    record: float  # from begin_frame until flush; 0 if begin_frame wasn't called
+    submit: float  # flushing the canvas to the surface
+    present: float  # handing the surface to the window, including any readback
+
+
+# frames taking longer than this many seconds are logged at debug level
+SLOW_FRAME_THRESHOLD = 1 / 30
+
+_last_frame_timings: Optional[FrameTimings] = None
+
+
+def get_frame_timings() -> Optional[FrameTimings]:
+    return _last_frame_timings
+
+
+class Painter(core.PainterHelpers):
     def __init__(self, frame: core.Frame, surface: skia.Surface):
         self._frame = frame
-        self._surface = surface
-        self._canvas = surface.getCanvas()
+        self._skia_surface = surface
+        self._skia_canvas = surface.getCanvas()
         self._style: Optional[core.Style] = None
         self._style_stack = []
+        self._frame_started: Optional[float] = None
+        # a GPU surface is only usable from the thread owning its GL context
+        self._thread = threading.current_thread()
+
+    # every drawing or readback call goes through these, so one from another
+    # thread fails before it touches the surface
+    @property
+    def _surface(self) -> skia.Surface:
+        self._check_thread()
+        return self._skia_surface
+
+    @property
+    def _canvas(self) -> skia.Canvas:
+        self._check_thread()
+        return self._skia_canvas
 
     def clear_all(self) -> None:
         self._frame.clear()
 
     def fill_rect(self, rect: core.Rect) -> None:
         style = cast(core.Style, self._style)
-        paint = skia.Paint(
-            Color=_to_skia_color(style.fill.color),
-            Style=skia.Paint.kFill_Style,
-        )
+        paint = _make_fill_paint(style.fill)
         sr = _to_skia_rect(rect)
         self._canvas.drawRect(sr, paint)
 
@@ -76,13 +399,30 @@ class Painter:
         sr = _to_skia_rect(rect)
         self._canvas.drawRect(sr, paint)
 
-    def fill_circle(self, circle: core.Circle) -> None:
-        style = cast(core.Style, self._style)
+    def fill_inset_rect(self, rect: core.Rect, insets: core.EdgeInsets) -> None:
+        self.fill_rect(rect.inset(insets))
+
+    def stroke_inset_rect(self, rect: core.Rect, insets: core.EdgeInsets) -> None:
+        self.stroke_rect(rect.inset(insets))
+
+    def stroke_outline(self, rect: core.Rect) -> None:
+        outline = cast(core.Style, self._style).outline
+        if outline is None:
+            return
         paint = skia.Paint(
-            Color=_to_skia_color(style.fill.color),
-            Style=skia.Paint.kFill_Style,
+            Color=_to_skia_color(outline.color),
+            Style=skia.Paint.kStroke_Style,
+            StrokeWidth=outline.width,
             AntiAlias=True,
         )
+        # a stroke is centered on its path
+        sr = _to_skia_rect(rect.inflate(outline.offset + outline.width / 2))
+        radius = outline.radius + outline.offset + outline.width / 2
+        self._canvas.drawRoundRect(sr, radius, radius, paint)
+
+    def fill_circle(self, circle: core.Circle) -> None:
+        style = cast(core.Style, self._style)
+        paint = _make_fill_paint(style.fill, anti_alias=True)
         c = circle.center
         self._canvas.drawCircle(c.x, c.y, circle.radius, paint)
 
@@ -96,6 +436,57 @@ class Painter:
         c = circle.center
         self._canvas.drawCircle(c.x, c.y, circle.radius, paint)
 
+    def draw_line(self, start: core.Point, end: core.Point) -> None:
+        paint = _make_stroke_paint(cast(core.Style, self._style))
+        self._canvas.drawLine(start.x, start.y, end.x, end.y, paint)
+
+    def stroke_polyline(self, points: Sequence[core.Point]) -> None:
+        paint = _make_stroke_paint(cast(core.Style, self._style))
+        self._canvas.drawPath(_to_skia_poly(points, False), paint)
+
+    def fill_polygon(self, points: Sequence[core.Point]) -> None:
+        style = cast(core.Style, self._style)
+        paint = _make_fill_paint(style.fill, anti_alias=True)
+        self._canvas.drawPath(_to_skia_poly(points, True), paint)
+
+    def stroke_polygon(self, points: Sequence[core.Point], close: bool = True) -> None:
+        paint = _make_stroke_paint(cast(core.Style, self._style))
+        self._canvas.drawPath(_to_skia_poly(points, close), paint)
+
+    def draw_arc(
+        self,
+        circle: core.Circle,
+        start_angle: float,
+        sweep_angle: float,
+        mode: core.ArcMode = core.ArcMode.OPEN,
+    ) -> None:
+        style = cast(core.Style, self._style)
+        c = circle.center
+        r = circle.radius
+        oval = skia.Rect.MakeLTRB(c.x - r, c.y - r, c.x + r, c.y + r)
+        if mode is core.ArcMode.OPEN:
+            paint = _make_stroke_paint(style)
+            self._canvas.drawArc(oval, start_angle, sweep_angle, False, paint)
+            return
+
+        paint = _make_fill_paint(style.fill, anti_alias=True)
+        if mode is core.ArcMode.PIE:
+            self._canvas.drawArc(oval, start_angle, sweep_angle, True, paint)
+        else:
+            chord = skia.Path()
+            chord.addArc(oval, start_angle, sweep_angle)
+            chord.close()
+            self._canvas.drawPath(chord, paint)
+
+    def fill_path(self, path: core.Path) -> None:
+        style = cast(core.Style, self._style)
+        paint = _make_fill_paint(style.fill, anti_alias=True)
+        self._canvas.drawPath(_to_skia_path(path), paint)
+
+    def stroke_path(self, path: core.Path) -> None:
+        paint = _make_stroke_paint(cast(core.Style, self._style))
+        self._canvas.drawPath(_to_skia_path(path), paint)
+
     def measure_text(self, text: str) -> float:
         style = cast(core.Style, self._style)
         font = _to_skia_font(style.font)
@@ -109,6 +500,9 @@ class Painter:
     def translate(self, pos: core.Point) -> None:
         self._canvas.translate(pos.x, pos.y)
 
+    def transform(self, transform: core.Transform) -> None:
+        self._canvas.concat(skia.Matrix.MakeAll(*transform.matrix))
+
     def clip(self, rect: core.Rect) -> None:
         self._canvas.clipRect(
             _to_skia_rect(
@@ -125,9 +519,9 @@ class Painter:
 
         style = cast(core.Style, self._style)
         if style is None or style.fill is None:
-            color = 0
+            paint = skia.Paint(Style=skia.Paint.kFill_Style, Color=0)
         else:
-            color = _to_skia_color(style.fill.color)
+            paint = _make_fill_paint(style.fill)
 
         if style is None or style.font is None:
             font = skia.Font(None, 0)
@@ -135,10 +529,6 @@ class Painter:
             font = _to_skia_font(style.font)
 
         blob = skia.TextBlob(text, font)
-        paint = skia.Paint(
-            Style=skia.Paint.kFill_Style,
-            Color=color,
-        )
         self._canvas.drawTextBlob(
             blob,
             pos.x,
@@ -152,21 +542,86 @@ class Painter:
         ...
 
     def draw_image(
-        self, file_path: str, rect: core.Rect, use_cache: bool = True
+        self, file_path: core.FilePath, rect: core.Rect, use_cache: bool = True
     ) -> None:
+        file_path = core.as_path_str(file_path)
         if use_cache:
             image = _get_cached_image(file_path)
         else:
-            image = skia.Image.open(file_path)
+            image = _open_image(file_path)
         self._canvas.drawImageRect(image, _to_skia_rect(rect))
 
-    def measure_image(self, file_path: str, use_cache: bool = True) -> core.Size:
+    def measure_image(
+        self, file_path: core.FilePath, use_cache: bool = True
+    ) -> core.Size:
+        file_path = core.as_path_str(file_path)
+        if use_cache:
+            image = _image_cache.get_if_present(file_path, _image_version(file_path))
+            if image is not None:
+                return core.Size(image.width(), image.height())
+
+        # the probe applies EXIF orientation as decoding does, so a rotated photo
+        # measures the same either way
+        header = image_header.probe(file_path)
+        if header is not None:
+            return core.Size(header.width, header.height)
+
         if use_cache:
             image = _get_cached_image(file_path)
         else:
-            image = skia.Image.open(file_path)
+            image = _open_image(file_path)
         return core.Size(image.width(), image.height())
 
+    def draw_image_tiled(
+        self,
+        file_path: core.FilePath,
+        rect: core.Rect,
+        tile_mode: core.TileMode = core.TileMode.REPEAT,
+        use_cache: bool = True,
+    ) -> None:
+        file_path = core.as_path_str(file_path)
+        if use_cache:
+            image = _get_cached_image(file_path)
+        else:
+            image = _open_image(file_path)
+        mode = _to_skia_tile_mode(tile_mode)
+        paint = skia.Paint(Shader=image.makeShader(mode, mode))
+        self._canvas.save()
+        # tiles start at the top left corner of rect rather than of the canvas
+        self._canvas.translate(rect.origin.x, rect.origin.y)
+        self._canvas.drawRect(
+            _to_skia_rect(core.Rect(core.Point(0, 0), rect.size)), paint
+        )
+        self._canvas.restore()
+
+    def load_svg(self, source: core.FilePath | bytes) -> core.SvgDocument:
+        if isinstance(source, bytes):
+            stream = skia.MemoryStream(skia.Data.MakeWithCopy(source))
+            return _load_svg(stream, "bytes")
+        return _open_svg(core.as_path_str(source))
+
+    def draw_svg(
+        self,
+        svg: core.FilePath | core.SvgDocument,
+        rect: core.Rect,
+        use_cache: bool = True,
+    ) -> None:
+        if not isinstance(svg, core.SvgDocument):
+            path = core.as_path_str(svg)
+            svg = _get_cached_svg(path) if use_cache else _open_svg(path)
+        dom = svg.document
+        width, height = svg.size.width, svg.size.height
+        self._canvas.save()
+        self._canvas.translate(rect.origin.x, rect.origin.y)
+        self._canvas.clipRect(skia.Rect.MakeWH(rect.size.width, rect.size.height))
+        if width > 0 and height > 0:
+            # drawn at its own size and scaled, so one document fits any rect
+            self._canvas.scale(rect.size.width / width, rect.size.height / height)
+        else:
+            dom.setContainerSize(skia.Size(rect.size.width, rect.size.height))
+        dom.render(self._canvas)
+        self._canvas.restore()
+
     def draw_net_image(self, url: str, rect: core.Rect, use_cache: bool = True) -> None:
         if use_cache:
             image = _get_cached_net_image(url)
@@ -185,8 +640,23 @@ class Painter:
         height, width, _ = array.shape
         return core.Size(width, height)
 
-    def get_net_image_async(self, name: str, url: str, callback) -> None:
-        raise NotImplementedError()
+    def get_image_async(
+        self, name: str, file_path: core.FilePath, callback
+    ) -> Optional[skia.Image]:
+        file_path = core.as_path_str(file_path)
+        try:
+            version = _image_version(file_path)
+        except OSError:
+            # the decode fails too, and records the failure for this version
+            version = None
+        return _request_image(
+            name, lambda _: _open_image(file_path), callback, version
+        )
+
+    def get_net_image_async(
+        self, name: str, url: str, callback
+    ) -> Optional[skia.Image]:
+        return _request_image(name, lambda _: _get_net_image(url), callback)
 
     def draw_image_object(self, img, x: float, y: float) -> None:
         self._canvas.drawImage(img, x, y)
@@ -201,6 +671,39 @@ class Painter:
         image = skia.Image.fromarray(array)
         self._canvas.drawImageRect(image, _to_skia_rect(rect))
 
+    # copies the surface into buffer, laid out as the surface itself is, with
+    # tightly packed rows. a failed readback raises rather than leaving the
+    # previous frame in buffer
+    def read_pixels_into(self, buffer) -> None:
+        info = self._surface.imageInfo()
+        size = info.computeMinByteSize()
+        if memoryview(buffer).nbytes < size:
+            raise ValueError(
+                f"Buffer of {memoryview(buffer).nbytes} bytes can't hold the "
+                f"{info.width()}x{info.height()} frame, which needs {size}"
+            )
+        if not self._surface.readPixels(info, buffer, info.minRowBytes()):
+            raise RuntimeError("Failed to read back the frame's pixels")
+
+    # (height, width, 4) RGBA, unpremultiplied
+    def to_numpy(self) -> np.ndarray:
+        return self._surface.makeImageSnapshot().toarray()
+
+    # file is a path or anything with a binary write(), e.g. BytesIO or a socket
+    # file; quality only affects the lossy formats
+    def save_image(
+        self,
+        file: core.FilePath | BinaryIO,
+        format: core.ImageFormat = core.ImageFormat.PNG,
+        quality: int = 90,
+    ) -> None:
+        data = encode_image(self._surface.makeImageSnapshot(), format, quality)
+        if hasattr(file, "write"):
+            file.write(data)
+        else:
+            with open(file, "wb") as f:
+                f.write(data)
+
     def get_numpy_image_async(self, array: np.ndarray, callback):
         raise NotImplementedError()
 
@@ -212,23 +715,708 @@ class Painter:
         self._canvas.restore()
         self._style = self._style_stack.pop()
 
+    def save_layer(
+        self,
+        bounds: Optional[core.Rect] = None,
+        filter: Optional[core.ImageFilter] = None,
+    ) -> None:
+        paint = skia.Paint(ImageFilter=_to_skia_image_filter(filter))
+        sr = None if bounds is None else _to_skia_rect(bounds)
+        self._canvas.saveLayer(sr, paint)
+        self._style_stack.append(self._style)
+
+    def save_count(self) -> int:
+        return len(self._style_stack)
+
     def style(self, style: core.Style) -> None:
         self._style = style
 
+    def begin_frame(self) -> None:
+        self._check_thread()
+        self._frame_started = time.perf_counter()
+
     def flush(self) -> None:
+        global _last_frame_timings
+        flush_started = time.perf_counter()
         self._canvas.flush()
+        submitted = time.perf_counter()
         self._frame.flush()
+        presented = time.perf_counter()
+
+        if self._frame_started is None:
+            record = 0.0
+        else:
+            record = flush_started - self._frame_started
+        self._frame_started = None
+        _last_frame_timings = FrameTimings(
+            record, submitted - flush_started, presented - submitted
+        )
+        if record + presented - flush_started > SLOW_FRAME_THRESHOLD:
+            logger.debug("Slow frame: %s", _last_frame_timings)
+
+    def _check_thread(self) -> None:
+        current = threading.current_thread()
+        if current is not self._thread:
+            raise RuntimeError(
+                f"Painter used from thread {current.name!r}, but it belongs to "
+                f"thread {self._thread.name!r}; "
+                "use Frame.post_update to redraw from other threads"
+            )
+
+
+@dataclass(slots=True, frozen=True)
+class ImageCacheStats:
+    hits: int
+    misses: int
+    evictions: int
+    usage: int
+    limit: int
+
+
+class _ImageCache:
+    def __init__(self, limit: int):
+        self._images: OrderedDict[str, tuple[skia.Image, object]] = OrderedDict()
+        self._limit = limit
+        self._usage = 0
+        self._hits = 0
+        self._misses = 0
+        self._evictions = 0
+        self._lock = threading.Lock()
+        self._loading: dict[str, threading.Event] = {}
+        # versions of images that failed to load, so they aren't retried every
+        # frame; a new version of the file gets another try
+        self._failures: dict[str, object] = {}
+
+    def get(
+        self, key: str, load: Callable[[str], skia.Image], version: object = None
+    ) -> skia.Image:
+        with self._lock:
+            image = self._lookup(key, version)
+            if image is not None:
+                return image
+            loading = self._loading.get(key)
+            if loading is None:
+                self._loading[key] = threading.Event()
+
+        if loading is not None:
+            # another thread is decoding the same image; reuse its result
+            loading.wait()
+            return self.get(key, load, version)
+
+        # decoding happens outside the lock so that a background decode
+        # doesn't block drawing of images already in the cache
+        try:
+            image = load(key)
+            self.put(key, image, version)
+        finally:
+            with self._lock:
+                self._loading.pop(key).set()
+        return image
+
+    def put(self, key: str, image: skia.Image, version: object = None) -> None:
+        with self._lock:
+            self._remove(key)
+            self._failures.pop(key, None)
+            self._images[key] = (image, version)
+            self._usage += _byte_size(image)
+            self._evict()
+
+    def get_if_present(self, key: str, version: object = None) -> Optional[skia.Image]:
+        with self._lock:
+            return self._lookup(key, version)
+
+    def mark_failed(self, key: str, version: object = None) -> None:
+        with self._lock:
+            self._failures[key] = version
+
+    def has_failed(self, key: str, version: object = None) -> bool:
+        with self._lock:
+            return key in self._failures and self._failures[key] == version
+
+    def remove(self, key: str) -> bool:
+        with self._lock:
+            # failures are stored with a version of None, so test membership
+            failed = key in self._failures
+            self._failures.pop(key, None)
+            return self._remove(key) or failed
+
+    def set_limit(self, limit: int) -> None:
+        with self._lock:
+            self._limit = limit
+            self._evict()
+
+    def clear(self) -> None:
+        with self._lock:
+            self._images.clear()
+            self._failures.clear()
+            self._usage = 0
+
+    def stats(self) -> ImageCacheStats:
+        with self._lock:
+            return ImageCacheStats(
+                hits=self._hits,
+                misses=self._misses,
+                evictions=self._evictions,
+                usage=self._usage,
+                limit=self._limit,
+            )
+
+    def _lookup(self, key: str, version: object) -> Optional[skia.Image]:
+        entry = self._images.get(key)
+        if entry is None or entry[1] != version:
+            self._misses += 1
+            return None
+
+        self._images.move_to_end(key)
+        self._hits += 1
+        return entry[0]
+
+    def _remove(self, key: str) -> bool:
+        entry = self._images.pop(key, None)
+        if entry is None:
+            return False
+
+        self._usage -= _byte_size(entry[0])
+        return True
+
+    def _evict(self) -> None:
+        # the most recently used image is kept even if it alone exceeds the limit
+        while self._usage > self._limit and len(self._images) > 1:
+            key, (image, _) = self._images.popitem(last=False)
+            self._usage -= _byte_size(image)
+            self._evictions += 1
+            logger.debug("Evicted %s from the image cache", key)
+
+
+def _byte_size(image: skia.Image) -> int:
+    return image.width() * image.height() * image.imageInfo().bytesPerPixel()
+
+
+DEFAULT_IMAGE_CACHE_LIMIT = 256 * 1024 * 1024
+
+_image_cache = _ImageCache(DEFAULT_IMAGE_CACHE_LIMIT)
+
+
+def set_image_cache_limit(limit: int) -> None:
+    _image_cache.set_limit(limit)
+
+
+def get_image_cache_stats() -> ImageCacheStats:
+    return _image_cache.stats()
+
+
+def clear_image_cache() -> None:
+    _image_cache.clear()
+    _get_versioned_svg.cache_clear()
+
+
+_pending_callbacks: dict[str, list[Callable[[], None]]] = {}
+_pending_lock = threading.Lock()
+
+
+def _request_image(
+    key: str,
+    load: Callable[[str], skia.Image],
+    callback: Callable[[], None],
+    version: object = None,
+) -> Optional[skia.Image]:
+    image = _image_cache.get_if_present(key, version)
+    if image is not None or _image_cache.has_failed(key, version):
+        return image
+
+    with _pending_lock:
+        if key in _pending_callbacks:
+            _pending_callbacks[key].append(callback)
+            return None
+        _pending_callbacks[key] = [callback]
+
+    threading.Thread(
+        target=_decode_image, args=(key, load, version), daemon=True
+    ).start()
+    return None
+
+
+def _decode_image(
+    key: str, load: Callable[[str], skia.Image], version: object
+) -> None:
+    try:
+        _image_cache.put(key, load(key), version)
+    except Exception:
+        # there is no caller to raise to on a background thread. the callbacks
+        # still run, and their redraw finds the failure instead of retrying
+        logger.warning("Failed to load image %s", key, exc_info=True)
+        _image_cache.mark_failed(key, version)
+    finally:
+        with _pending_lock:
+            callbacks = _pending_callbacks.pop(key)
+    for callback in callbacks:
+        callback()
+
+
+def remove_image_from_cache(path: core.FilePath) -> bool:
+    return _image_cache.remove(core.as_path_str(path))
+
+
+_prefetcher: Optional[ThreadPoolExecutor] = None
+
+
+def prefetch_images(paths: Iterable[core.FilePath]) -> None:
+    global _prefetcher
+    if _prefetcher is None:
+        _prefetcher = ThreadPoolExecutor(thread_name_prefix="castella-prefetch")
+    for path in paths:
+        _prefetcher.submit(_prefetch_image, core.as_path_str(path))
+
+
+def _prefetch_image(path: str) -> None:
+    # skia decodes encoded images lazily on first draw, so force it here
+    try:
+        _image_cache.get(
+            path, lambda p: _open_image(p).makeRasterImage(), _image_version(path)
+        )
+    except Exception:
+        logger.warning("Failed to prefetch image %s", path, exc_info=True)
+
+
+DEFAULT_IMAGE_DECODE_LIMIT = 100_000_000
+
+_image_decode_limit: Optional[int] = DEFAULT_IMAGE_DECODE_LIMIT
+
+
+def set_image_decode_limit(max_pixels: Optional[int]) -> None:
+    global _image_decode_limit
+    _image_decode_limit = max_pixels
+
+
+def _open_image(path: str) -> skia.Image:
+    _check_decode_limit(path, image_header.probe(path))
+    try:
+        return skia.Image.open(path)
+    except RuntimeError as e:
+        with open(path, "rb") as f:
+            data = f.read()
+        raise RuntimeError(_describe_decode_failure(path, data)) from e
+
+
+# formats skia-python's prebuilt wheels decode
+_DECODABLE_FORMATS = frozenset({"png", "jpeg", "gif", "bmp", "webp", "ico"})
+
+
+# distinguishes a corrupt or truncated download from a format skia can't read
+def _describe_decode_failure(source: str, data: bytes) -> str:
+    if not data:
+        return f"Failed to decode {source}: it is empty"
+    found = image_header.sniff_format(data)
+    if found is None:
+        return (
+            f"Failed to decode {source}: {len(data)} bytes in no recognized "
+            "image format"
+        )
+    if found not in _DECODABLE_FORMATS:
+        return (
+            f"Failed to decode {source}: {found} ({len(data)} bytes) isn't "
+            "supported by this build of skia"
+        )
+    if (
+        found in image_header.PROBED_FORMATS
+        and image_header.probe_bytes(data) is None
+    ):
+        problem = "its header is truncated"
+    elif image_header.looks_truncated(found, data):
+        problem = "it looks truncated"
+    else:
+        problem = "it may be corrupt"
+    return f"Failed to decode {source} as {found} ({len(data)} bytes): {problem}"
+
+
+def _check_decode_limit(
+    source: str, header: Optional[image_header.ImageHeader]
+) -> None:
+    # formats the header probe doesn't know are decoded unchecked
+    if _image_decode_limit is None or header is None:
+        return
+    if header.width * header.height > _image_decode_limit:
+        raise RuntimeError(
+            f"{source} is {header.width}x{header.height}, which exceeds "
+            f"the decode limit of {_image_decode_limit} pixels"
+        )
 
 
-@cache
 def _get_cached_image(path: str) -> skia.Image:
-    return skia.Image.open(path)
+    return _image_cache.get(path, _open_image, _image_version(path))
+
+
+def _image_version(path: str) -> tuple[int, int]:
+    # a changed modification time or size means the file was edited on disk
+    st = os.stat(path)
+    return st.st_mtime_ns, st.st_size
+
+
+DEFAULT_SVG_CACHE_LIMIT = 64
+
+
+def _get_cached_svg(path: str) -> core.SvgDocument:
+    return _get_versioned_svg(path, _image_version(path))
+
+
+# keyed by version too, so an edited file is parsed again. a failed parse
+# raises, and lru_cache doesn't keep it
+@lru_cache(maxsize=DEFAULT_SVG_CACHE_LIMIT)
+def _get_versioned_svg(path: str, _: tuple[int, int]) -> core.SvgDocument:
+    return _open_svg(path)
+
+
+def _open_svg(path: str) -> core.SvgDocument:
+    stream = skia.Stream.MakeFromFile(path)
+    if stream is None:
+        raise ValueError(f"Failed to open SVG: {path}")
+    return _load_svg(stream, path)
+
+
+def _load_svg(stream: skia.Stream, source: str) -> core.SvgDocument:
+    dom = skia.SVGDOM.MakeFromStream(stream)
+    if dom is None:
+        raise ValueError(f"Failed to parse SVG from {source}")
+    size = dom.containerSize()
+    return core.SvgDocument(dom, core.Size(size.width(), size.height()))
+
+
+# the result is a raster copy, so a thumbnail pays for high-quality scaling once
+# rather than on every draw
+def resize_image(
+    image: skia.Image,
+    width: int,
+    height: int,
+    quality: skia.FilterQuality = skia.FilterQuality.kHigh_FilterQuality,
+) -> skia.Image:
+    if width <= 0 or height <= 0:
+        raise ValueError(f"Invalid image size: {width}x{height}")
+    resized = image.resize(width, height, quality)
+    if resized is None:
+        raise RuntimeError(f"Failed to resize the image to {width}x{height}")
+    return resized
+
+
+# quality only affects the lossy formats
+def encode_image(
+    image: skia.Image,
+    format: core.ImageFormat = core.ImageFormat.PNG,
+    quality: int = 90,
+) -> bytes:
+    data = image.encodeToData(_to_skia_image_format(format), quality)
+    if data is None:
+        raise RuntimeError(f"Failed to encode the image as {format.name}")
+    return bytes(data)
+
+
+# shares the source's pixels where skia can, rather than copying them
+def crop_image(image: skia.Image, rect: core.Rect) -> skia.Image:
+    subset = skia.IRect.MakeXYWH(
+        round(rect.origin.x),
+        round(rect.origin.y),
+        round(rect.size.width),
+        round(rect.size.height),
+    )
+    cropped = image.makeSubset(subset)
+    if cropped is None:
+        raise ValueError(
+            f"Crop {rect} is empty or outside the "
+            f"{image.width()}x{image.height()} image"
+        )
+    return cropped
+
+
+# weighted by alpha, so transparent pixels don't pull the color toward black
+def image_average_color(image: skia.Image) -> core.Color:
+    pixels = image.toarray().reshape(-1, 4).astype(np.float64)
+    alpha = pixels[:, 3]
+    total = alpha.sum()
+    if total == 0:
+        return core.Color(0, 0, 0, 0)
+    r, g, b = np.rint((pixels[:, :3] * alpha[:, None]).sum(axis=0) / total)
+    return core.Color(int(r), int(g), int(b), int(np.rint(alpha.mean())))
+
+
+# the most common colors, most common first, after bucketing each channel to
+# 3 bits. each is its bucket's mean, so it isn't snapped to the bucket's corner
+def image_palette(image: skia.Image, count: int = 5) -> list[core.Color]:
+    pixels = image.toarray().reshape(-1, 4)
+    # mostly transparent pixels say little about how the image looks
+    rgb = pixels[pixels[:, 3] >= 128][:, :3].astype(np.int32)
+    buckets = ((rgb[:, 0] >> 5) << 6) | ((rgb[:, 1] >> 5) << 3) | (rgb[:, 2] >> 5)
+    counts = np.bincount(buckets, minlength=512)
+    palette = []
+    for bucket in np.argsort(counts)[::-1][:count]:
+        if counts[bucket] == 0:
+            break
+        r, g, b = np.rint(rgb[buckets == bucket].mean(axis=0))
+        palette.append(core.Color(int(r), int(g), int(b)))
+    return palette
+
+
+# None when the image is fully transparent
+def image_dominant_color(image: skia.Image) -> Optional[core.Color]:
+    palette = image_palette(image, 1)
+    return palette[0] if palette else None
+
+
+# frames decode on first use and stay cached, since a frame that isn't a
+# keyframe is drawn over the one it depends on
+class AnimatedImage:
+    def __init__(self, codec: skia.Codec, source: str):
+        self._codec = codec
+        self._source = source
+        self._frame_infos = codec.getFrameInfo()
+        size = codec.dimensions()
+        self._info = skia.ImageInfo.Make(
+            size.width(),
+            size.height(),
+            skia.kRGBA_8888_ColorType,
+            skia.kUnpremul_AlphaType,
+        )
+        self._pixels: dict[int, np.ndarray] = {}
+        self._frames: dict[int, skia.Image] = {}
+
+    @property
+    def size(self) -> core.Size:
+        return core.Size(self._info.width(), self._info.height())
+
+    @property
+    def frame_count(self) -> int:
+        return max(1, self._codec.getFrameCount())
+
+    # total playback time of one loop in milliseconds
+    @property
+    def duration(self) -> int:
+        return sum(self.frame_duration(i) for i in range(self.frame_count))
+
+    def frame_duration(self, index: int) -> int:
+        self._check_index(index)
+        if not self._frame_infos:
+            return 0
+        return self._frame_infos[index].fDuration
+
+    # -1 loops forever
+    @property
+    def repetition_count(self) -> int:
+        return self._codec.getRepetitionCount()
+
+    # the frame showing at time_ms, wrapping around the loop
+    def frame_index_at(self, time_ms: float) -> int:
+        total = self.duration
+        if total <= 0:
+            return 0
+        t = time_ms % total
+        for i in range(self.frame_count):
+            t -= self.frame_duration(i)
+            if t < 0:
+                return i
+        return self.frame_count - 1
+
+    def frame(self, index: int) -> skia.Image:
+        self._check_index(index)
+        image = self._frames.get(index)
+        if image is None:
+            image = skia.Image.fromarray(self._decode(index))
+            self._frames[index] = image
+        return image
+
+    def _check_index(self, index: int) -> None:
+        if not 0 <= index < self.frame_count:
+            raise IndexError(
+                f"Frame {index} is out of range for {self._source}, "
+                f"which has {self.frame_count} frames"
+            )
+
+    def _decode(self, index: int) -> np.ndarray:
+        pixels = self._pixels.get(index)
+        if pixels is not None:
+            return pixels
+        options = skia.Codec.Options()
+        options.fFrameIndex = index
+        required = self._frame_infos[index].fRequiredFrame if self._frame_infos else -1
+        if required >= 0:
+            pixels = self._decode(required).copy()
+            options.fPriorFrame = required
+        else:
+            pixels = np.zeros(
+                (self._info.height(), self._info.width(), 4), dtype=np.uint8
+            )
+        result = self._codec.getPixels(
+            self._info, pixels, self._info.minRowBytes(), options
+        )
+        # a truncated file still yields the rows that arrived
+        if result not in (
+            skia.Codec.Result.kSuccess,
+            skia.Codec.Result.kIncompleteInput,
+        ):
+            raise RuntimeError(
+                f"Failed to decode frame {index} of {self._source}: {result}"
+            )
+        self._pixels[index] = pixels
+        return pixels
+
+
+# GIF, WebP and APNG all decode through skia's codec; a still image loads as
+# a single frame
+def load_animated_image(source: core.FilePath | bytes) -> AnimatedImage:
+    if isinstance(source, bytes):
+        data, name = source, "bytes"
+    else:
+        name = core.as_path_str(source)
+        with open(name, "rb") as f:
+            data = f.read()
+    _check_decode_limit(name, image_header.probe_bytes(data))
+    codec = skia.Codec.MakeFromData(skia.Data.MakeWithCopy(data))
+    if codec is None:
+        raise RuntimeError(_describe_decode_failure(name, data))
+    if codec.getFrameCount() <= 1 and image_header.is_apng(data):
+        # skia builds without APNG support decode only the default image
+        logger.warning(
+            "%s is an animated PNG, but this build of skia decodes only its "
+            "first frame",
+            name,
+        )
+    return AnimatedImage(codec, name)
 
 
-@cache
 def _get_cached_net_image(url: str) -> skia.Image:
-    return _get_net_image(url)
+    return _image_cache.get(url, _get_net_image)
 
 
 def _get_net_image(url: str) -> skia.Image:
-    return skia.Image.open(io.BytesIO(urllib.request.urlopen(url).read()))
+    data = _fetch(url)
+    _check_decode_limit(url, image_header.probe_bytes(data))
+    try:
+        return skia.Image.open(io.BytesIO(data))
+    except RuntimeError as e:
+        raise RuntimeError(_describe_decode_failure(url, data)) from e
+
+
+_net_image_cache_dir: Optional[str] = None
+
+# seconds to wait for a server, since draw_net_image fetches on the render
+# thread
+NET_IMAGE_TIMEOUT = 10.0
+
+
+def set_net_image_cache_dir(path: Optional[str]) -> None:
+    global _net_image_cache_dir
+    _net_image_cache_dir = path
+
+
+def _fetch(url: str) -> bytes:
+    if _net_image_cache_dir is None:
+        with urllib.request.urlopen(url, timeout=NET_IMAGE_TIMEOUT) as response:
+            return response.read()
+
+    name = hashlib.sha256(url.encode()).hexdigest()
+    body_path = os.path.join(_net_image_cache_dir, name)
+    meta_path = body_path + ".json"
+
+    request = urllib.request.Request(url)
+    meta = _read_cache_meta(meta_path) if os.path.exists(body_path) else None
+    if meta is not None:
+        if meta.get("expires", 0) > time.time():
+            return _read_cached_body(body_path)
+        if "etag" in meta:
+            request.add_header("If-None-Match", meta["etag"])
+        if "last_modified" in meta:
+            request.add_header("If-Modified-Since", meta["last_modified"])
+
+    try:
+        with urllib.request.urlopen(request, timeout=NET_IMAGE_TIMEOUT) as response:
+            data = response.read()
+            headers = response.headers
+    except urllib.error.HTTPError as e:
+        if meta is None:
+            raise
+        if e.code == 304:
+            logger.debug("Reusing the cached copy of %s", url)
+            # a 304 can extend the cached copy's lifetime
+            _write_atomically(meta_path, json.dumps(_cache_meta(e.headers, meta)))
+        else:
+            logger.warning(
+                "Failed to revalidate %s (HTTP %d), using the cached copy", url, e.code
+            )
+        return _read_cached_body(body_path)
+    except OSError:
+        # a network error or timeout
+        if meta is None:
+            raise
+        logger.warning("Failed to revalidate %s, using the cached copy", url)
+        return _read_cached_body(body_path)
+
+    if "no-store" in _cache_directives(headers.get("Cache-Control")):
+        return data
+    os.makedirs(_net_image_cache_dir, exist_ok=True)
+    # the body goes first, so metadata is only ever next to a complete body
+    _write_atomically(body_path, data)
+    _write_atomically(meta_path, json.dumps(_cache_meta(headers, {})))
+    return data
+
+
+def _read_cached_body(body_path: str) -> bytes:
+    with open(body_path, "rb") as f:
+        return f.read()
+
+
+# unreadable metadata, e.g. from a crash in an older version, is a cache miss
+def _read_cache_meta(meta_path: str) -> Optional[dict]:
+    try:
+        with open(meta_path) as f:
+            meta = json.load(f)
+    except (OSError, ValueError):
+        return None
+    return meta if isinstance(meta, dict) else None
+
+
+# concurrent fetches of one URL each write their own temporary file, and the
+# last replace wins whole
+def _write_atomically(path: str, data: bytes | str) -> None:
+    if isinstance(data, str):
+        data = data.encode()
+    fd, temp_path = tempfile.mkstemp(dir=os.path.dirname(path))
+    try:
+        with os.fdopen(fd, "wb") as f:
+            f.write(data)
+        os.replace(temp_path, path)
+    except BaseException:
+        os.unlink(temp_path)
+        raise
+
+
+# the validators and expiry time for a response, over those already stored
+def _cache_meta(headers, previous: dict) -> dict:
+    meta = dict(previous)
+    if headers.get("ETag") is not None:
+        meta["etag"] = headers["ETag"]
+    if headers.get("Last-Modified") is not None:
+        meta["last_modified"] = headers["Last-Modified"]
+    max_age = _max_age(headers.get("Cache-Control"))
+    if max_age is None:
+        meta.pop("expires", None)
+    else:
+        meta["expires"] = time.time() + max_age
+    return meta
+
+
+def _cache_directives(cache_control: Optional[str]) -> list[str]:
+    return [d.strip().lower() for d in (cache_control or "").split(",")]
+
+
+def _max_age(cache_control: Optional[str]) -> Optional[int]:
+    directives = _cache_directives(cache_control)
+    # no-cache still stores the response, but revalidates every use
+    if "no-cache" in directives or "no-store" in directives:
+        return None
+    for directive in directives:
+        name, _, value = directive.partition("=")
+        if name.strip() == "max-age" and value.strip().isdigit():
+            return int(value)
+    return None
+    for directive in directives:
+        name, _, value = directive.partition("=")
+        if name.strip() == "max-age" and value.strip().isdigit():
+            return int(value)
+    return None
@@ -25,7 +25,7 @@ class Frame:
                 dict_converter=Object.fromEntries,
             ),
         )
-        self._painter = Painter(self, self._surface)
+        self._painter = core.instrument(Painter(self, self._surface))
 
     def on_mouse_down(self, handler: Callable[[core.MouseEvent], None]) -> None:
         self._add_mouse_down = lambda: document.body.addEventListener(
@@ -104,6 +104,7 @@ class Frame:
             pos = w.get_pos()
             clippedRect = core.Rect(core.Point(0, 0), w.get_size())
 
+        depth = self._painter.save_count()
         self._painter.save()
         try:
             self._painter.translate(pos)
@@ -112,7 +113,7 @@ class Frame:
             ev.target.redraw(self._painter, ev.completely)
             self._painter.flush()
         finally:
-            self._painter.restore()
+            self._painter.restore_to_count(depth)
 
     def flush(self) -> None:
         pass